@@ -0,0 +1,159 @@
+use crypto::hash::keccak256;
+use ethereum_types::{Address, H256, U256, U64};
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+use serde::{Deserialize, Serialize};
+use web3_signer::error::RecoveryError;
+use crate::bytes::Bytes;
+use crate::raw_transaction::recover_sender;
+use crate::typed_transaction::{append_to, decode_to, h256_from_u256, with_tx_type, ACCESS_LIST_TX_TYPE};
+
+/// EIP-2930 access list 항목
+/// # Example
+/// * {"address":"0x...","storageKeys":["0x...","0x..."]}
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct AccessListItem {
+    pub address: Address,
+    #[serde(rename="storageKeys")]
+    pub storage_keys: Vec<H256>,
+}
+
+impl Encodable for AccessListItem {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(2);
+        s.append(&self.address);
+        s.append_list(&self.storage_keys);
+    }
+}
+
+impl Decodable for AccessListItem {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 2 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        Ok(Self {
+            address: rlp.val_at(0)?,
+            storage_keys: rlp.list_at(1)?,
+        })
+    }
+}
+
+/// EIP-2930 (type 1) 트랜잭션
+/// `0x01 || rlp([chainId, nonce, gasPrice, gasLimit, to, value, data, accessList, yParity, r, s])`
+#[derive(Serialize, Clone, Debug)]
+pub struct AccessListTransaction {
+    #[serde(rename="chainId")]
+    pub chain_id: U64,
+    pub nonce: U64,
+    #[serde(rename="gasPrice")]
+    pub gas_price: U256,
+    pub gas: U64,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Bytes,
+    #[serde(rename="accessList")]
+    pub access_list: Vec<AccessListItem>,
+    #[serde(rename="yParity")]
+    pub y_parity: U64,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl AccessListTransaction {
+    /// 서명할 때 쓰는 해시. `keccak256(0x01 || rlp(서명을 뺀 필드))`
+    pub fn signing_hash(&self) -> H256 {
+        let mut stream = RlpStream::new();
+        self.rlp_append_unsigned(&mut stream);
+        keccak256(with_tx_type(ACCESS_LIST_TX_TYPE, stream.as_raw()).as_slice())
+    }
+
+    pub fn sender(&self) -> Result<Address, RecoveryError> {
+        recover_sender(self.signing_hash(), self.y_parity.as_u64() as i32, h256_from_u256(&self.r), h256_from_u256(&self.s))
+    }
+
+    fn rlp_append_unsigned(&self, s: &mut RlpStream) {
+        s.begin_list(8);
+        self.rlp_append_fields(s);
+    }
+
+    fn rlp_append_fields(&self, s: &mut RlpStream) {
+        s.append(&self.chain_id);
+        s.append(&self.nonce);
+        s.append(&self.gas_price);
+        s.append(&self.gas);
+        append_to(s, &self.to);
+        s.append(&self.value);
+        s.append(&self.input.0);
+        s.append_list(&self.access_list);
+    }
+}
+
+/// type 바이트를 뺀 payload
+impl Encodable for AccessListTransaction {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(11);
+        self.rlp_append_fields(s);
+        s.append(&self.y_parity);
+        s.append(&self.r);
+        s.append(&self.s);
+    }
+}
+
+impl Decodable for AccessListTransaction {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 11 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            nonce: rlp.val_at(1)?,
+            gas_price: rlp.val_at(2)?,
+            gas: rlp.val_at(3)?,
+            to: decode_to(&rlp.at(4)?)?,
+            value: rlp.val_at(5)?,
+            input: Bytes(rlp.val_at(6)?),
+            access_list: rlp.list_at(7)?,
+            y_parity: rlp.val_at(8)?,
+            r: rlp.val_at(9)?,
+            s: rlp.val_at(10)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use ethereum_types::{Address, BigEndianHash, H256, U256};
+    use crate::typed_transaction::TypedTransaction;
+
+    /// chain id 1, nonce 9, access list 1개. 개인키 0x4646...46 (EIP-155 예제와 같은 키)으로 서명
+    const RAW: &str = "01f8a701098504a817c800827530943535353535353535353535353535353535353535880de0b6b3a764000080f838f7940000000000000000000000000000000000000001e1a0000000000000000000000000000000000000000000000000000000000000000080a02178f3d96701ec4803620ff00a5c022f686d567660aa8b565b1944c4d0d062f7a0538d893a5ffc4c0e144ebfdeef107111e92c1ae4c4b9583cef9c31088c26148b";
+
+    #[test]
+    fn signed_access_list_transaction() {
+        let raw = hex::decode(RAW).unwrap();
+        let tx = TypedTransaction::decode(raw.as_slice()).unwrap();
+        let atx = match &tx {
+            TypedTransaction::AccessList(atx) => atx,
+            _ => panic!("not an access list transaction"),
+        };
+        assert_eq!(atx.access_list.len(), 1);
+        assert_eq!(atx.access_list[0].storage_keys, vec![H256::zero()]);
+        assert_eq!(tx.chain_id(), Some(1));
+        assert_eq!(tx.signing_hash(), H256::from_str("620b57e7ed6f50cdaee3b7bf2a8fd6becf1b7b94b8c98b67f8fc1ddfae55d828").unwrap());
+        assert_eq!(tx.hash(raw.as_slice()), H256::from_str("21695c54be2a5b3e4bee7b05b7865a20b15238c9efc4a7ee66be471d7d4400ec").unwrap());
+        assert_eq!(tx.sender().unwrap(), Address::from_str("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f").unwrap());
+        assert_eq!(tx.encode(), raw);
+    }
+
+    /// 서명한 뒤에 access list를 바꾸면 다른 주소가 나온다.
+    #[test]
+    fn tampered_access_list() {
+        let raw = hex::decode(RAW).unwrap();
+        let mut atx = match TypedTransaction::decode(raw.as_slice()).unwrap() {
+            TypedTransaction::AccessList(atx) => atx,
+            _ => panic!("not an access list transaction"),
+        };
+        atx.access_list[0].storage_keys[0] = H256::from_uint(&U256::one());
+        assert_ne!(atx.sender().ok(), Some(Address::from_str("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f").unwrap()));
+    }
+}
@@ -0,0 +1,331 @@
+use crypto::hash::keccak256;
+use ethereum_types::{Address, H256, U256, U64};
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use web3_signer::error::RecoveryError;
+use crate::access_list_transaction::AccessListItem;
+use crate::block::BlockHeader;
+use crate::bytes::Bytes;
+use crate::raw_transaction::recover_sender;
+use crate::typed_transaction::{h256_from_u256, with_tx_type, BLOB_TX_TYPE};
+
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+pub const BYTES_PER_BLOB: usize = 131072;
+pub const BYTES_PER_COMMITMENT: usize = 48;
+pub const BYTES_PER_PROOF: usize = 48;
+pub const GAS_PER_BLOB: u64 = 131072;
+pub const MIN_BASE_FEE_PER_BLOB_GAS: u64 = 1;
+pub const TARGET_BLOB_GAS_PER_BLOCK: u64 = 393216;
+pub const MAX_BLOB_GAS_PER_BLOCK: u64 = 786432;
+pub const BLOB_BASE_FEE_UPDATE_FRACTION: u64 = 3338477;
+
+/// EIP-4844 (type 3) 트랜잭션
+/// canonical form: `0x03 || rlp([chainId, nonce, maxPriorityFeePerGas, maxFeePerGas, gasLimit, to, value, data,
+/// accessList, maxFeePerBlobGas, blobVersionedHashes, yParity, r, s])`
+/// network form: `0x03 || rlp([canonical payload, blobs, commitments, proofs])`
+/// blob 트랜잭션은 컨트랙트를 만들 수 없으므로 `to`가 반드시 있다.
+#[derive(Serialize, Clone, Debug)]
+pub struct BlobTransaction {
+    #[serde(rename="chainId")]
+    pub chain_id: U64,
+    pub nonce: U64,
+    #[serde(rename="maxPriorityFeePerGas")]
+    pub max_priority_fee_per_gas: U256,
+    #[serde(rename="maxFeePerGas")]
+    pub max_fee_per_gas: U256,
+    pub gas: U64,
+    pub to: Address,
+    pub value: U256,
+    pub input: Bytes,
+    #[serde(rename="accessList")]
+    pub access_list: Vec<AccessListItem>,
+    #[serde(rename="maxFeePerBlobGas")]
+    pub max_fee_per_blob_gas: U256,
+    #[serde(rename="blobVersionedHashes")]
+    pub blob_versioned_hashes: Vec<H256>,
+    #[serde(rename="yParity")]
+    pub y_parity: U64,
+    pub r: U256,
+    pub s: U256,
+    /// network form으로 받았을 때만 있다.
+    #[serde(skip)]
+    pub sidecar: Option<BlobSidecar>,
+}
+
+/// network form에 함께 실려 오는 blob, KZG commitment, proof
+#[derive(Clone, Debug, Default)]
+pub struct BlobSidecar {
+    pub blobs: Vec<Bytes>,
+    pub commitments: Vec<Bytes>,
+    pub proofs: Vec<Bytes>,
+}
+
+impl BlobTransaction {
+    /// network form과 canonical form을 모두 받는다. (type 바이트를 뺀 payload)
+    pub fn decode_payload(payload: &[u8]) -> Result<Self, DecoderError> {
+        let rlp = Rlp::new(payload);
+        match rlp.item_count()? {
+            4 => {
+                let mut tx: BlobTransaction = rlp.val_at(0)?;
+                tx.sidecar = Some(BlobSidecar {
+                    blobs: rlp.list_at::<Vec<u8>>(1)?.into_iter().map(Bytes).collect(),
+                    commitments: rlp.list_at::<Vec<u8>>(2)?.into_iter().map(Bytes).collect(),
+                    proofs: rlp.list_at::<Vec<u8>>(3)?.into_iter().map(Bytes).collect(),
+                });
+                Ok(tx)
+            }
+            _ => rlp.as_val(),
+        }
+    }
+
+    /// 서명할 때 쓰는 해시. `keccak256(0x03 || rlp(서명을 뺀 필드))`
+    pub fn signing_hash(&self) -> H256 {
+        let mut stream = RlpStream::new();
+        stream.begin_list(11);
+        self.rlp_append_fields(&mut stream);
+        keccak256(with_tx_type(BLOB_TX_TYPE, stream.as_raw()).as_slice())
+    }
+
+    /// 트랜잭션 해시. network form으로 받았어도 sidecar를 뺀 canonical form으로 계산한다.
+    pub fn hash(&self) -> H256 {
+        keccak256(with_tx_type(BLOB_TX_TYPE, rlp::encode(self).as_ref()).as_slice())
+    }
+
+    pub fn sender(&self) -> Result<Address, RecoveryError> {
+        recover_sender(self.signing_hash(), self.y_parity.as_u64() as i32, h256_from_u256(&self.r), h256_from_u256(&self.s))
+    }
+
+    /// 이 트랜잭션이 쓰는 blob gas
+    pub fn blob_gas_used(&self) -> u64 {
+        GAS_PER_BLOB * self.blob_versioned_hashes.len() as u64
+    }
+
+    /// blob과 관계된 필드를 검사한다. 문제가 있으면 이유를 돌려준다.
+    /// KZG proof 자체는 검증하지 않는다.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.blob_versioned_hashes.is_empty() {
+            return Err("blob transaction missing blob hashes".to_string());
+        }
+        if let Some(hash) = self.blob_versioned_hashes.iter().find(|hash| hash[0] != VERSIONED_HASH_VERSION_KZG) {
+            return Err(format!("blob hash {:?} has unsupported version 0x{:02x}", hash, hash[0]));
+        }
+        if self.blob_gas_used() > MAX_BLOB_GAS_PER_BLOCK {
+            return Err(format!("too many blobs in transaction: have {}, permitted {}",
+                self.blob_versioned_hashes.len(), MAX_BLOB_GAS_PER_BLOCK / GAS_PER_BLOB));
+        }
+        if self.max_fee_per_blob_gas < U256::from(MIN_BASE_FEE_PER_BLOB_GAS) {
+            return Err(format!("max fee per blob gas {} is less than the minimum blob base fee {}", self.max_fee_per_blob_gas, MIN_BASE_FEE_PER_BLOB_GAS));
+        }
+        let sidecar = match &self.sidecar {
+            Some(sidecar) => sidecar,
+            None => return Ok(()),
+        };
+        let count = self.blob_versioned_hashes.len();
+        if sidecar.blobs.len() != count || sidecar.commitments.len() != count || sidecar.proofs.len() != count {
+            return Err(format!("invalid number of {} blobs compared to {} commitments, {} proofs and {} blob hashes",
+                sidecar.blobs.len(), sidecar.commitments.len(), sidecar.proofs.len(), count));
+        }
+        for (index, blob) in sidecar.blobs.iter().enumerate() {
+            if blob.0.len() != BYTES_PER_BLOB {
+                return Err(format!("blob {} has {} bytes, expected {}", index, blob.0.len(), BYTES_PER_BLOB));
+            }
+        }
+        for (index, proof) in sidecar.proofs.iter().enumerate() {
+            if proof.0.len() != BYTES_PER_PROOF {
+                return Err(format!("proof {} has {} bytes, expected {}", index, proof.0.len(), BYTES_PER_PROOF));
+            }
+        }
+        for (index, (commitment, hash)) in sidecar.commitments.iter().zip(self.blob_versioned_hashes.iter()).enumerate() {
+            if commitment.0.len() != BYTES_PER_COMMITMENT {
+                return Err(format!("commitment {} has {} bytes, expected {}", index, commitment.0.len(), BYTES_PER_COMMITMENT));
+            }
+            let computed = kzg_to_versioned_hash(commitment.as_slice());
+            if computed != *hash {
+                return Err(format!("blob {}: computed hash {:?} mismatches transaction one {:?}", index, computed, hash));
+            }
+        }
+        Ok(())
+    }
+
+    fn rlp_append_fields(&self, s: &mut RlpStream) {
+        s.append(&self.chain_id);
+        s.append(&self.nonce);
+        s.append(&self.max_priority_fee_per_gas);
+        s.append(&self.max_fee_per_gas);
+        s.append(&self.gas);
+        s.append(&self.to);
+        s.append(&self.value);
+        s.append(&self.input.0);
+        s.append_list(&self.access_list);
+        s.append(&self.max_fee_per_blob_gas);
+        s.append_list(&self.blob_versioned_hashes);
+    }
+}
+
+/// `0x01 || sha256(commitment)[1..]`
+pub fn kzg_to_versioned_hash(commitment: &[u8]) -> H256 {
+    let mut hash = H256::from_slice(Sha256::digest(commitment).as_slice());
+    hash.0[0] = VERSIONED_HASH_VERSION_KZG;
+    hash
+}
+
+/// `parent` 다음 블록의 excess blob gas. `max(0, excess_blob_gas + blob_gas_used - TARGET_BLOB_GAS_PER_BLOCK)`
+pub fn calc_excess_blob_gas(parent: &BlockHeader) -> u64 {
+    parent.excess_blob_gas.saturating_add(parent.blob_gas_used).saturating_sub(TARGET_BLOB_GAS_PER_BLOCK)
+}
+
+/// blob gas 가격. `fake_exponential(MIN_BASE_FEE_PER_BLOB_GAS, excess_blob_gas, BLOB_BASE_FEE_UPDATE_FRACTION)`
+/// 가격이 U256 범위를 넘으면 `U256::MAX`를 돌려준다.
+pub fn blob_gas_price(excess_blob_gas: u64) -> U256 {
+    let factor = U256::from(MIN_BASE_FEE_PER_BLOB_GAS);
+    let numerator = U256::from(excess_blob_gas);
+    let denominator = U256::from(BLOB_BASE_FEE_UPDATE_FRACTION);
+    let mut output = U256::zero();
+    let mut accum = factor * denominator;
+    let mut i = U256::one();
+    while !accum.is_zero() {
+        output = match output.checked_add(accum) {
+            Some(output) => output,
+            None => return U256::MAX,
+        };
+        accum = match accum.checked_mul(numerator) {
+            Some(product) => product / (denominator * i),
+            None => return U256::MAX,
+        };
+        i += U256::one();
+    }
+    output / denominator
+}
+
+/// type 바이트를 뺀 canonical payload
+impl Encodable for BlobTransaction {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(14);
+        self.rlp_append_fields(s);
+        s.append(&self.y_parity);
+        s.append(&self.r);
+        s.append(&self.s);
+    }
+}
+
+impl Decodable for BlobTransaction {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 14 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            nonce: rlp.val_at(1)?,
+            max_priority_fee_per_gas: rlp.val_at(2)?,
+            max_fee_per_gas: rlp.val_at(3)?,
+            gas: rlp.val_at(4)?,
+            to: rlp.val_at(5)?,
+            value: rlp.val_at(6)?,
+            input: Bytes(rlp.val_at(7)?),
+            access_list: rlp.list_at(8)?,
+            max_fee_per_blob_gas: rlp.val_at(9)?,
+            blob_versioned_hashes: rlp.list_at(10)?,
+            y_parity: rlp.val_at(11)?,
+            r: rlp.val_at(12)?,
+            s: rlp.val_at(13)?,
+            sidecar: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use ethereum_types::{Address, H256, U256};
+    use rlp::RlpStream;
+    use crate::typed_transaction::{with_tx_type, TypedTransaction};
+    use super::{blob_gas_price, kzg_to_versioned_hash, BYTES_PER_BLOB, BYTES_PER_COMMITMENT, BYTES_PER_PROOF};
+
+    /// canonical form. chain id 1, nonce 1, maxFeePerBlobGas 1 gwei, blob 1개 (0으로 채운 blob)
+    /// 개인키 0x4646...46으로 서명
+    const RAW: &str = "03f8920101843b9aca008506fc23ac008252089435353535353535353535353535353535353535358080c0843b9aca00e1a0010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c44401480a0f3600156c4ce1530f0c30e65b8c3759f281a8ba4c4f7c1a5ec3f74c4ccc9f76ba002c7c0b71a5783fbd58b82dc9f9c7bd4a0353e38691c4de6e429e93abe635287";
+    const HASH: &str = "e1dbf68a2f75f0881ae37cbb2d1f198724c6d63dc8d93d0b9a9534c81b947dc5";
+    /// 0으로 채운 blob의 versioned hash. commitment는 무한원점 `0xc0 00..00`이다.
+    const EMPTY_BLOB_VERSIONED_HASH: &str = "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014";
+
+    fn empty_blob_commitment() -> Vec<u8> {
+        let mut commitment = vec![0u8; BYTES_PER_COMMITMENT];
+        commitment[0] = 0xc0;
+        commitment
+    }
+
+    /// canonical payload에 blob, commitment, proof를 붙여 network form을 만든다.
+    fn network_form(commitment: Vec<u8>) -> Vec<u8> {
+        let raw = hex::decode(RAW).unwrap();
+        let mut stream = RlpStream::new_list(4);
+        stream.append_raw(&raw[1..], 1);
+        stream.begin_list(1).append(&vec![0u8; BYTES_PER_BLOB]);
+        stream.begin_list(1).append(&commitment);
+        stream.begin_list(1).append(&empty_blob_commitment()[..BYTES_PER_PROOF].to_vec());
+        with_tx_type(raw[0], stream.as_raw())
+    }
+
+    #[test]
+    fn signed_blob_transaction() {
+        let raw = hex::decode(RAW).unwrap();
+        let tx = TypedTransaction::decode(raw.as_slice()).unwrap();
+        assert!(matches!(tx, TypedTransaction::Blob(_)));
+        assert_eq!(tx.signing_hash(), H256::from_str("8b45b5a4ae6607e99759aeb60b0976e1749f1da6184da9c117f92c664a56ecb6").unwrap());
+        assert_eq!(tx.hash(raw.as_slice()), H256::from_str(HASH).unwrap());
+        assert_eq!(tx.sender().unwrap(), Address::from_str("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f").unwrap());
+        assert_eq!(tx.blob_gas_used(), 131072);
+        assert!(tx.validate().is_ok());
+    }
+
+    #[test]
+    fn empty_blob_versioned_hash() {
+        assert_eq!(kzg_to_versioned_hash(empty_blob_commitment().as_slice()), H256::from_str(EMPTY_BLOB_VERSIONED_HASH).unwrap());
+    }
+
+    /// network form으로 받아도 해시와 보낸 사람은 canonical form과 같다.
+    #[test]
+    fn network_form_blob_transaction() {
+        let network = network_form(empty_blob_commitment());
+        let tx = TypedTransaction::decode(network.as_slice()).unwrap();
+        assert_eq!(tx.hash(network.as_slice()), H256::from_str(HASH).unwrap());
+        assert_eq!(tx.sender().unwrap(), Address::from_str("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f").unwrap());
+        assert_eq!(tx.encode(), hex::decode(RAW).unwrap());
+        assert!(tx.validate().is_ok());
+    }
+
+    #[test]
+    fn commitment_mismatch() {
+        let mut commitment = empty_blob_commitment();
+        commitment[47] = 1;
+        let network = network_form(commitment);
+        let tx = TypedTransaction::decode(network.as_slice()).unwrap();
+        assert!(tx.validate().unwrap_err().contains("mismatches"));
+    }
+
+    /// 한 블록에 다 들어가지 않는 blob 트랜잭션은 받지 않는다.
+    #[test]
+    fn too_many_blobs() {
+        let raw = hex::decode(RAW).unwrap();
+        let mut tx = TypedTransaction::decode(raw.as_slice()).unwrap();
+        if let TypedTransaction::Blob(btx) = &mut tx {
+            let hash = btx.blob_versioned_hashes[0];
+            btx.blob_versioned_hashes = vec![hash; 7];
+        }
+        assert!(tx.validate().unwrap_err().starts_with("too many blobs"));
+    }
+
+    /// go-ethereum `TestCalcBlobFee`와 같은 값
+    #[test]
+    fn blob_gas_price_vectors() {
+        assert_eq!(blob_gas_price(0), U256::from(1));
+        assert_eq!(blob_gas_price(2314057), U256::from(1));
+        assert_eq!(blob_gas_price(2314058), U256::from(2));
+        assert_eq!(blob_gas_price(10 * 1024 * 1024), U256::from(23));
+    }
+
+    #[test]
+    fn blob_gas_price_overflow() {
+        assert_eq!(blob_gas_price(u64::MAX), U256::MAX);
+    }
+}
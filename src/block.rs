@@ -1,42 +1,140 @@
-use ethereum_types::{Address, H256};
-use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
-use serde::{Serialize, Deserialize};
+use crypto::hash::keccak256;
+use ethereum_types::{Address, Bloom, H256, H64, U256, U64};
+use rlp::{Encodable, RlpStream};
+use serde::Serialize;
+use crate::bytes::Bytes;
 use crate::transaction::Transaction;
 
+/// 블록 헤더 (Prague)
+/// 블록 해시는 RLP로 인코딩한 헤더의 keccak256이다.
+/// PoS 이후 블록이므로 difficulty, nonce, ommers는 항상 비어 있다.
+#[derive(Clone, Debug)]
+pub struct BlockHeader {
+    pub parent_hash: H256,
+    pub ommers_hash: H256,
+    pub beneficiary: Address,
+    pub state_root: H256,
+    pub transactions_root: H256,
+    pub receipts_root: H256,
+    pub logs_bloom: Bloom,
+    pub difficulty: U256,
+    pub number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: Bytes,
+    pub mix_hash: H256,
+    pub nonce: H64,
+    pub base_fee_per_gas: U256,
+    pub withdrawals_root: H256,
+    pub blob_gas_used: u64,
+    pub excess_blob_gas: u64,
+    pub parent_beacon_block_root: H256,
+    pub requests_hash: H256,
+}
+
+impl BlockHeader {
+    pub fn hash(&self) -> H256 {
+        keccak256(rlp::encode(self).as_ref())
+    }
+}
+
+impl Encodable for BlockHeader {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(21);
+        s.append(&self.parent_hash);
+        s.append(&self.ommers_hash);
+        s.append(&self.beneficiary);
+        s.append(&self.state_root);
+        s.append(&self.transactions_root);
+        s.append(&self.receipts_root);
+        s.append(&self.logs_bloom);
+        s.append(&self.difficulty);
+        s.append(&self.number);
+        s.append(&self.gas_limit);
+        s.append(&self.gas_used);
+        s.append(&self.timestamp);
+        s.append(&self.extra_data.0);
+        s.append(&self.mix_hash);
+        s.append(&self.nonce);
+        s.append(&self.base_fee_per_gas);
+        s.append(&self.withdrawals_root);
+        s.append(&self.blob_gas_used);
+        s.append(&self.excess_blob_gas);
+        s.append(&self.parent_beacon_block_root);
+        s.append(&self.requests_hash);
+    }
+}
+
+/// 만들어진 블록
+/// # [Fields]
+/// - `header: BlockHeader`         - 헤더
+/// - `hash: H256`                  - 헤더 해시
+/// - `transactions: Vec<H256>`     - 들어 있는 트랜잭션 해시 (순서대로)
+/// - `size: u64`                   - RLP로 인코딩한 블록의 바이트 수
+#[derive(Clone, Debug)]
+pub struct SealedBlock {
+    pub header: BlockHeader,
+    pub hash: H256,
+    pub transactions: Vec<H256>,
+    pub size: u64,
+}
+
+impl SealedBlock {
+    pub fn number(&self) -> u64 {
+        self.header.number
+    }
+}
+
+/// 블록에 든 트랜잭션. `full_transactions`가 false면 해시만 보낸다.
+#[derive(Serialize, Clone)]
+#[serde(untagged)]
+pub enum BlockTransactions {
+    Hashes(Vec<H256>),
+    Full(Vec<Transaction>),
+}
 
 /// Ethereum Block structure
 /// Web3와 블록 데이터를 주고 받을 때 사용한다.
 /// # [Fields]
-/// - `number: u64`                     - the block number. null when its pending block.
-/// - `hash: H256`                      - hash of the block. null when its pending block.
+/// - `number: U64`                     - the block number.
+/// - `hash: H256`                      - hash of the block.
 /// - `parent_hash: H256`               - hash of the parent block.
-/// - `nonce: [u8;8]`                   - hash of the generated proof-of-work. null when its pending block.
+/// - `nonce: H64`                      - hash of the generated proof-of-work. always zero.
 /// - `sha3_uncles: H256`               - SHA3 of the uncles data in the block.
-/// - `logs_bloom: [u8;256]`            - the bloom filter for the logs of the block. null when its pending block.
+/// - `logs_bloom: Bloom`               - the bloom filter for the logs of the block.
 /// - `transactions_root: H256`         - the root of the transaction trie of the block.
 /// - `state_root: H256`                - the root of the final state trie of the block.
-/// - `receipts_root: H256`              - the root of the receipts trie of the block.
+/// - `receipts_root: H256`             - the root of the receipts trie of the block.
 /// - `miner: Address`                  - the address of the beneficiary to whom the mining rewards were given.
-/// - `difficulty: u64`                 - integer of the difficulty for this block.
-/// - `total_difficulty: u64`           - integer of the total difficulty of the chain until this block.
-/// - `extra_data: Vec<u8>`             - the "extra data" field of this block.
-/// - `size: u64`                       - integer the size of this block in bytes.
-/// - `gas_limit: u64`                  - the maximum gas allowed in this block.
-/// - `gas_used: u64`                   - the total used gas by all transactions in this block.
-/// - `timestamp: u64`                  - the unix timestamp for when the block was collated.
-/// - `transactions: Vec<Transaction>`  - Array of tx objects, or 32 Bytes tx hashes depending on the last given parameter.
-/// - `uncles: Vec<H256>`               - Array of uncle hashes.
-#[derive(Serialize, Deserialize)]
-pub struct PseudoBlock {
-    pub number: u64,
+/// - `difficulty: U256`                - integer of the difficulty for this block. always zero.
+/// - `total_difficulty: U256`          - integer of the total difficulty of the chain until this block.
+/// - `extra_data: Bytes`               - the "extra data" field of this block.
+/// - `size: U64`                       - integer the size of this block in bytes.
+/// - `gas_limit: U64`                  - the maximum gas allowed in this block.
+/// - `gas_used: U64`                   - the total used gas by all transactions in this block.
+/// - `timestamp: U64`                  - the unix timestamp for when the block was collated.
+/// - `mix_hash: H256`                  - prevRandao.
+/// - `base_fee_per_gas: U256`          - EIP-1559 base fee.
+/// - `withdrawals_root: H256`          - the root of the withdrawal trie. always empty.
+/// - `blob_gas_used: U64`              - EIP-4844 blob gas used by all transactions in this block.
+/// - `excess_blob_gas: U64`            - EIP-4844 excess blob gas.
+/// - `parent_beacon_block_root: H256`  - EIP-4788 beacon root. always zero.
+/// - `requests_hash: H256`             - EIP-7685 requests hash.
+/// - `transactions: BlockTransactions` - Array of tx objects, or 32 Bytes tx hashes depending on the last given parameter.
+/// - `uncles: Vec<H256>`               - Array of uncle hashes. always empty.
+/// - `withdrawals: Vec<Value>`         - Array of withdrawals. always empty.
+#[derive(Serialize, Clone)]
+pub struct Block {
+    pub number: U64,
     pub hash: H256,
     #[serde(rename="parentHash")]
     pub parent_hash: H256,
-    pub nonce: u64, //[u8; 8],
+    pub nonce: H64,
     #[serde(rename="sha3Uncles")]
-    pub sha3_uncles: Vec<H256>,
+    pub sha3_uncles: H256,
     #[serde(rename="logsBloom")]
-    pub logs_bloom: String,
+    pub logs_bloom: Bloom,
     #[serde(rename="transactionsRoot")]
     pub transactions_root: H256,
     #[serde(rename="stateRoot")]
@@ -44,70 +142,67 @@ pub struct PseudoBlock {
     #[serde(rename="receiptsRoot")]
     pub receipts_root: H256,
     pub miner: Address,
-    pub difficulty: u64,
+    pub difficulty: U256,
     #[serde(rename="totalDifficulty")]
-    pub total_difficulty: u64,
+    pub total_difficulty: U256,
     #[serde(rename="extraData")]
-    pub extra_data: Vec<u8>,
-    pub size: u64,
+    pub extra_data: Bytes,
+    pub size: U64,
     #[serde(rename="gasLimit")]
-    pub gas_limit: u64,
+    pub gas_limit: U64,
     #[serde(rename="gasUsed")]
-    pub gas_used: u64,
-    pub timestamp: u64,
-    pub transactions: Vec<Transaction>,
-    //
-    pub uncles: Vec<H256>,                  // always zero-vector
+    pub gas_used: U64,
+    pub timestamp: U64,
+    #[serde(rename="mixHash")]
+    pub mix_hash: H256,
+    #[serde(rename="baseFeePerGas")]
+    pub base_fee_per_gas: U256,
+    #[serde(rename="withdrawalsRoot")]
+    pub withdrawals_root: H256,
+    #[serde(rename="blobGasUsed")]
+    pub blob_gas_used: U64,
+    #[serde(rename="excessBlobGas")]
+    pub excess_blob_gas: U64,
+    #[serde(rename="parentBeaconBlockRoot")]
+    pub parent_beacon_block_root: H256,
+    #[serde(rename="requestsHash")]
+    pub requests_hash: H256,
+    pub transactions: BlockTransactions,
+    pub uncles: Vec<H256>,
+    pub withdrawals: Vec<serde_json::Value>,
 }
 
-impl Default for PseudoBlock {
-    fn default() -> Self {
+impl Block {
+    pub fn new(block: &SealedBlock, transactions: BlockTransactions) -> Self {
+        let header = &block.header;
         Self {
-            number: 0,
-            hash: H256::zero(),
-            parent_hash: H256::zero(),
-            nonce: 0,
-            sha3_uncles: vec![],
-            logs_bloom: hex::encode([0u8;256].to_vec()),
-            transactions_root: H256::zero(),
-            state_root: H256::zero(),
-            receipts_root: H256::zero(),
-            miner: Address::zero(),
-            difficulty: 0,
-            total_difficulty: 0,
-            extra_data: vec![],
-            size: 0,
-            gas_limit: 0,
-            gas_used: 0,
-            timestamp: 0,
-            transactions: vec![],
+            number: header.number.into(),
+            hash: block.hash,
+            parent_hash: header.parent_hash,
+            nonce: header.nonce,
+            sha3_uncles: header.ommers_hash,
+            logs_bloom: header.logs_bloom,
+            transactions_root: header.transactions_root,
+            state_root: header.state_root,
+            receipts_root: header.receipts_root,
+            miner: header.beneficiary,
+            difficulty: header.difficulty,
+            total_difficulty: U256::zero(),
+            extra_data: header.extra_data.clone(),
+            size: block.size.into(),
+            gas_limit: header.gas_limit.into(),
+            gas_used: header.gas_used.into(),
+            timestamp: header.timestamp.into(),
+            mix_hash: header.mix_hash,
+            base_fee_per_gas: header.base_fee_per_gas,
+            withdrawals_root: header.withdrawals_root,
+            blob_gas_used: header.blob_gas_used.into(),
+            excess_blob_gas: header.excess_blob_gas.into(),
+            parent_beacon_block_root: header.parent_beacon_block_root,
+            requests_hash: header.requests_hash,
+            transactions,
             uncles: vec![],
+            withdrawals: vec![],
         }
     }
 }
-
-impl Clone for PseudoBlock {
-    fn clone(&self) -> Self {
-        Self {
-            number: self.number.clone(),
-            hash: self.hash.clone(),
-            parent_hash: self.parent_hash.clone(),
-            nonce: self.nonce.clone(),
-            sha3_uncles: self.sha3_uncles.to_vec(),
-            logs_bloom: self.logs_bloom.clone(),
-            transactions_root: self.transactions_root.clone(),
-            state_root: self.state_root.clone(),
-            receipts_root: self.receipts_root.clone(),
-            miner: self.miner.clone(),
-            difficulty: self.difficulty.clone(),
-            total_difficulty: self.total_difficulty.clone(),
-            extra_data: self.extra_data.to_vec(),
-            size: self.size.clone(),
-            gas_limit: self.gas_limit.clone(),
-            gas_used: self.gas_used.clone(),
-            timestamp: self.timestamp.clone(),
-            transactions: self.transactions.to_vec(),
-            uncles: self.uncles.to_vec(),
-        }
-    }
-}
\ No newline at end of file
@@ -0,0 +1,66 @@
+use std::fmt::{Display, Formatter};
+use serde::{Deserialize, Deserializer};
+use serde::de::Error;
+
+/// 상태를 조회할 블록을 가리키는 파라미터
+/// # Example
+/// * "latest", "pending", "earliest", "safe", "finalized", "0x1b4"
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockTag {
+    Earliest,
+    Latest,
+    Pending,
+    Safe,
+    Finalized,
+    Number(u64),
+}
+
+impl BlockTag {
+    /// `head`가 최신 블록 번호일 때 가리키는 블록 번호
+    /// `pending`도 아직 블록이 없으므로 최신 블록 번호를 돌려준다.
+    pub fn resolve(&self, head: u64) -> u64 {
+        match self {
+            BlockTag::Earliest => 0,
+            BlockTag::Latest | BlockTag::Pending | BlockTag::Safe | BlockTag::Finalized => head,
+            BlockTag::Number(number) => *number,
+        }
+    }
+}
+
+impl Default for BlockTag {
+    fn default() -> Self {
+        BlockTag::Latest
+    }
+}
+
+impl Display for BlockTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlockTag::Earliest => write!(f, "earliest"),
+            BlockTag::Latest => write!(f, "latest"),
+            BlockTag::Pending => write!(f, "pending"),
+            BlockTag::Safe => write!(f, "safe"),
+            BlockTag::Finalized => write!(f, "finalized"),
+            BlockTag::Number(number) => write!(f, "0x{:x}", number),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockTag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let str_tag = String::deserialize(deserializer)?;
+        match str_tag.as_str() {
+            "earliest" => Ok(BlockTag::Earliest),
+            "latest" => Ok(BlockTag::Latest),
+            "pending" => Ok(BlockTag::Pending),
+            "safe" => Ok(BlockTag::Safe),
+            "finalized" => Ok(BlockTag::Finalized),
+            _ => match str_tag.strip_prefix("0x") {
+                Some(hex_number) if !hex_number.is_empty() => u64::from_str_radix(hex_number, 16)
+                    .map(BlockTag::Number)
+                    .map_err(|_| D::Error::custom(format!("invalid block number '{}'", str_tag))),
+                _ => Err(D::Error::custom(format!("invalid block tag '{}'", str_tag))),
+            },
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use ethereum_types::H256;
+use crate::block::SealedBlock;
+use crate::block_tag::BlockTag;
+
+struct ChainInner {
+    /// 번호 순서대로 쌓인 블록. 0번은 genesis다.
+    blocks: Vec<SealedBlock>,
+    by_hash: HashMap<H256, u64>,
+    /// 받았지만 아직 블록에 들어가지 않은 트랜잭션 (받은 순서)
+    pending: Vec<H256>,
+}
+
+/// 만들어진 블록과 다음 블록에 들어갈 트랜잭션
+/// 블록을 만드는 동안에는 새 트랜잭션을 받지 않으므로, 상태와 블록의 순서가 어긋나지 않는다.
+pub struct Blockchain {
+    inner: Mutex<ChainInner>,
+}
+
+impl Blockchain {
+    pub fn new(genesis: SealedBlock) -> Self {
+        let mut by_hash = HashMap::new();
+        by_hash.insert(genesis.hash, genesis.number());
+        Self {
+            inner: Mutex::new(ChainInner { blocks: vec![genesis], by_hash, pending: vec![] }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ChainInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 최신 블록
+    pub fn head(&self) -> SealedBlock {
+        self.lock().blocks.last().cloned().expect("the genesis block is always present")
+    }
+
+    /// 최신 블록 번호
+    pub fn number(&self) -> u64 {
+        self.lock().blocks.len() as u64 - 1
+    }
+
+    pub fn block_by_number(&self, number: u64) -> Option<SealedBlock> {
+        self.lock().blocks.get(number as usize).cloned()
+    }
+
+    pub fn block_by_hash(&self, hash: &H256) -> Option<SealedBlock> {
+        let inner = self.lock();
+        inner.by_hash.get(hash).and_then(|number| inner.blocks.get(*number as usize)).cloned()
+    }
+
+    /// `block`이 가리키는 블록. 아직 만들지 않은 블록은 없으므로 `pending`은 최신 블록을 돌려준다.
+    pub fn block(&self, block: BlockTag) -> Option<SealedBlock> {
+        let number = block.resolve(self.number());
+        self.block_by_number(number)
+    }
+
+    /// 다음 블록에 들어갈 트랜잭션
+    pub fn pending(&self) -> Vec<H256> {
+        self.lock().pending.clone()
+    }
+
+    /// `apply(head)`가 성공하면 트랜잭션을 다음 블록에 넣는다. `head`는 다음 블록의 부모가 될 최신 블록이다.
+    /// 블록을 만드는 중이면 끝날 때까지 기다린다.
+    pub fn submit<F>(&self, hash: H256, apply: F) -> Result<(), String>
+        where F: FnOnce(&SealedBlock) -> Result<(), String> {
+        let mut inner = self.lock();
+        apply(inner.blocks.last().expect("the genesis block is always present"))?;
+        inner.pending.push(hash);
+        Ok(())
+    }
+
+    /// 기다리는 트랜잭션을 모두 꺼내 `build(parent, transactions)`로 다음 블록을 만들고 체인에 붙인다.
+    /// `build`가 함께 돌려준 트랜잭션은 블록에 들어가지 못한 것이므로 다음 블록을 위해 다시 기다리게 한다.
+    pub fn seal<F>(&self, build: F) -> SealedBlock
+        where F: FnOnce(&SealedBlock, Vec<H256>) -> (SealedBlock, Vec<H256>) {
+        let mut inner = self.lock();
+        let pending = std::mem::take(&mut inner.pending);
+        let (block, remaining) = build(inner.blocks.last().expect("the genesis block is always present"), pending);
+        inner.pending = remaining;
+        inner.by_hash.insert(block.hash, block.number());
+        inner.blocks.push(block.clone());
+        block
+    }
+}
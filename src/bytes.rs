@@ -0,0 +1,50 @@
+use std::fmt::{Debug, Formatter};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Error;
+
+/// `0x` 접두사가 붙은 16진수 문자열로 직렬화되는 바이트 배열
+/// # Example
+/// * "0x" (empty), "0x6080604052"
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Bytes(pub Vec<u8>);
+
+impl Bytes {
+    pub fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    fn from(data: Vec<u8>) -> Self {
+        Self(data)
+    }
+}
+
+impl Debug for Bytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{}", hex::encode(&self.0))
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(format!("0x{}", hex::encode(&self.0)).as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let str_bytes = String::deserialize(deserializer)?;
+        decode_hex(str_bytes.as_str()).map(Bytes).map_err(D::Error::custom)
+    }
+}
+
+/// `0x` 접두사가 있거나 없는 16진수 문자열을 바이트 배열로 바꾼다.
+/// 홀수 길이(`0x1`)는 앞에 0을 채워서 해석한다.
+pub fn decode_hex(str_hex: &str) -> Result<Vec<u8>, hex::FromHexError> {
+    let str_hex = str_hex.strip_prefix("0x").unwrap_or(str_hex);
+    match str_hex.len() % 2 {
+        0 => hex::decode(str_hex),
+        _ => hex::decode(format!("0{}", str_hex)),
+    }
+}
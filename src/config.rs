@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use ethereum_types::{Address, U256};
+use log::LevelFilter;
+use serde::Deserialize;
+use crate::cors::CorsConfig;
+use crate::filters::{DEFAULT_FILTER_TIMEOUT, DEFAULT_MAX_FILTERS};
+use crate::logs::DEFAULT_MAX_LOG_BLOCK_RANGE;
+use crate::miner::MiningMode;
+use crate::recorder::{DEFAULT_RECORD_MAX_FILES, DEFAULT_RECORD_MAX_SIZE};
+
+pub const DEFAULT_BIND_ADDRESS: &str = "0.0.0.0";
+pub const DEFAULT_PORT: u16 = 8545;
+pub const DEFAULT_WS_PORT: u16 = 8546;
+pub const DEFAULT_CHAIN_ID: u64 = 84; // 0x54
+pub const DEFAULT_SHUTDOWN_TIMEOUT: u64 = 5;
+pub const DEFAULT_CLIENT_VERSION: &str = "Biiot/v0.1.0/windows/rust1.52";
+pub const DEFAULT_BALANCE: u64 = 0xFFFFFFFFFFFFFFFF;
+
+pub const USAGE: &str = "Usage: metamask_sign_checker [OPTIONS]
+
+Options:
+    -c, --config <FILE>            TOML configuration file
+        --bind <ADDRESS>           address to listen on (default: 0.0.0.0)
+    -p, --port <PORT>              port to listen on (default: 8545)
+        --ws-port <PORT>           WebSocket port, 0 to disable (default: 8546)
+        --ipc-path <PATH>          Unix domain socket path for IPC (default: disabled)
+        --chain-id <ID>            chain id used by eth_chainId and the signer (default: 84)
+        --network-id <ID>          network id reported by net_version (default: chain id)
+        --client-version <STRING>  value reported by web3_clientVersion
+        --gas-price <WEI>          value reported by eth_gasPrice (default: 0)
+        --mining <MODE>            auto, interval or manual (default: auto)
+        --block-time <SECONDS>     block interval for interval mining (default: 0)
+        --max-log-block-range <BLOCKS>
+                                   widest block range eth_getLogs accepts, 0 for no limit (default: 10000)
+        --filter-timeout <SECONDS> remove filters not polled for this long (default: 300)
+        --max-filters <COUNT>      maximum number of installed filters (default: 1000)
+        --record <FILE>            append every request and response to a JSONL cassette (default: disabled)
+        --record-max-size <BYTES>  rotate the cassette when it grows past this size (default: 10485760)
+        --record-max-files <COUNT> rotated cassettes to keep (default: 5)
+        --log-level <LEVEL>        off, error, warn, info, debug, trace (default: info)
+        --shutdown-timeout <SECONDS>
+                                   time to wait for running requests on SIGINT/SIGTERM (default: 5)
+    -h, --help                     print this message";
+
+/// 노드 설정
+/// 설정 파일(TOML) -> 커맨드라인 인자 순서로 덮어쓴다.
+/// # [Fields]
+/// - `bind_address: String`        - listener가 바인드할 주소
+/// - `port: u16`                   - listener가 바인드할 포트
+/// - `ws_port: u16`                - WebSocket listener가 바인드할 포트. 0이면 띄우지 않는다.
+/// - `ipc_path: Option<String>`    - IPC 소켓 경로. 없으면 띄우지 않는다.
+/// - `chain_id: u64`               - EIP-155 chain id. eth_chainId, 서명 검증에 모두 이 값을 쓴다.
+/// - `network_id: Option<u64>`     - net_version 값. 없으면 chain_id와 같다.
+/// - `client_version: String`      - web3_clientVersion 값
+/// - `gas_price: u64`              - eth_gasPrice 값 (wei)
+/// - `mining: MiningMode`          - 블록을 만드는 시점 (auto, interval, manual)
+/// - `block_time: u64`             - interval 모드의 블록 생성 주기(초)
+/// - `max_log_block_range: u64`    - eth_getLogs가 한 번에 찾는 최대 블록 수. 0이면 제한하지 않는다.
+/// - `filter_timeout: u64`         - 이 시간(초) 동안 조회하지 않은 필터는 지운다.
+/// - `max_filters: usize`          - 한 번에 설치할 수 있는 필터 수
+/// - `record_path: Option<String>` - 요청과 응답을 남길 JSONL 파일. 없으면 남기지 않는다.
+/// - `record_max_size: u64`        - 파일이 이 크기(바이트)를 넘으면 새 파일에 쓴다.
+/// - `record_max_files: usize`     - 남겨 둘 이전 파일 수
+/// - `log_level: String`           - 로그 레벨
+/// - `shutdown_timeout: u64`       - 종료 시 처리 중인 요청을 기다리는 시간(초)
+/// - `default_balance: U256`       - 처음 보는 계정의 잔액 (wei)
+/// - `alloc: BTreeMap<Address, U256>` - genesis 계정별 잔액 (`[alloc]` 테이블)
+/// - `cors: CorsConfig`            - CORS 설정 (`[cors]` 테이블)
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Config {
+    pub bind_address: String,
+    pub port: u16,
+    pub ws_port: u16,
+    pub ipc_path: Option<String>,
+    pub chain_id: u64,
+    pub network_id: Option<u64>,
+    pub client_version: String,
+    pub gas_price: u64,
+    pub mining: MiningMode,
+    pub block_time: u64,
+    pub max_log_block_range: u64,
+    pub filter_timeout: u64,
+    pub max_filters: usize,
+    pub record_path: Option<String>,
+    pub record_max_size: u64,
+    pub record_max_files: usize,
+    pub log_level: String,
+    pub shutdown_timeout: u64,
+    pub default_balance: U256,
+    pub alloc: BTreeMap<Address, U256>,
+    pub cors: CorsConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            bind_address: DEFAULT_BIND_ADDRESS.to_string(),
+            port: DEFAULT_PORT,
+            ws_port: DEFAULT_WS_PORT,
+            ipc_path: None,
+            chain_id: DEFAULT_CHAIN_ID,
+            network_id: None,
+            client_version: DEFAULT_CLIENT_VERSION.to_string(),
+            gas_price: 0,
+            mining: MiningMode::default(),
+            block_time: 0,
+            max_log_block_range: DEFAULT_MAX_LOG_BLOCK_RANGE,
+            filter_timeout: DEFAULT_FILTER_TIMEOUT,
+            max_filters: DEFAULT_MAX_FILTERS,
+            record_path: None,
+            record_max_size: DEFAULT_RECORD_MAX_SIZE,
+            record_max_files: DEFAULT_RECORD_MAX_FILES,
+            log_level: "info".to_string(),
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            default_balance: U256::from(DEFAULT_BALANCE),
+            alloc: BTreeMap::new(),
+            cors: CorsConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    /// TOML 설정 파일을 읽는다.
+    pub fn from_file(path: &str) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::Io(path.to_string(), e.to_string()))?;
+        Self::from_toml(text.as_str())
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, ConfigError> {
+        toml::from_str(text).map_err(|e| ConfigError::Toml(e.to_string()))
+    }
+
+    /// 커맨드라인 인자로 설정을 만든다. (`args`에 프로그램 이름은 포함하지 않는다.)
+    /// `--config`가 있으면 먼저 파일을 읽고, 나머지 인자로 덮어쓴다.
+    pub fn from_args(args: &[String]) -> Result<Self, ConfigError> {
+        let mut config = Config::default();
+        if let Some(index) = args.iter().position(|arg| arg == "-c" || arg == "--config") {
+            let path = args.get(index + 1).ok_or(ConfigError::MissingValue(args[index].clone()))?;
+            config = Config::from_file(path)?;
+        }
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let mut value = || iter.next().cloned().ok_or(ConfigError::MissingValue(arg.clone()));
+            match arg.as_str() {
+                "-h" | "--help" => return Err(ConfigError::Help),
+                "-c" | "--config" => { value()?; }
+                "--bind" => config.bind_address = value()?,
+                "-p" | "--port" => config.port = parse_arg(arg, value()?.as_str())?,
+                "--ws-port" => config.ws_port = parse_arg(arg, value()?.as_str())?,
+                "--ipc-path" => config.ipc_path = Some(value()?),
+                "--chain-id" => config.chain_id = parse_arg(arg, value()?.as_str())?,
+                "--network-id" => config.network_id = Some(parse_arg(arg, value()?.as_str())?),
+                "--client-version" => config.client_version = value()?,
+                "--gas-price" => config.gas_price = parse_arg(arg, value()?.as_str())?,
+                "--mining" => config.mining = parse_arg(arg, value()?.as_str())?,
+                "--block-time" => config.block_time = parse_arg(arg, value()?.as_str())?,
+                "--max-log-block-range" => config.max_log_block_range = parse_arg(arg, value()?.as_str())?,
+                "--filter-timeout" => config.filter_timeout = parse_arg(arg, value()?.as_str())?,
+                "--max-filters" => config.max_filters = parse_arg(arg, value()?.as_str())?,
+                "--record" => config.record_path = Some(value()?),
+                "--record-max-size" => config.record_max_size = parse_arg(arg, value()?.as_str())?,
+                "--record-max-files" => config.record_max_files = parse_arg(arg, value()?.as_str())?,
+                "--log-level" => config.log_level = value()?,
+                "--shutdown-timeout" => config.shutdown_timeout = parse_arg(arg, value()?.as_str())?,
+                _ => return Err(ConfigError::UnknownArgument(arg.clone())),
+            }
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// 값끼리 맞지 않는 설정을 찾는다.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        self.log_level_filter()?;
+        if self.mining == MiningMode::Interval && self.block_time == 0 {
+            return Err(ConfigError::InvalidValue("block_time".to_string(), self.block_time.to_string()));
+        }
+        if self.record_max_size == 0 {
+            return Err(ConfigError::InvalidValue("record_max_size".to_string(), self.record_max_size.to_string()));
+        }
+        if self.filter_timeout == 0 {
+            return Err(ConfigError::InvalidValue("filter_timeout".to_string(), self.filter_timeout.to_string()));
+        }
+        Ok(())
+    }
+
+    /// net_version이 돌려줄 값
+    pub fn network_id(&self) -> u64 {
+        self.network_id.unwrap_or(self.chain_id)
+    }
+
+    pub fn log_level_filter(&self) -> Result<LevelFilter, ConfigError> {
+        LevelFilter::from_str(self.log_level.as_str())
+            .map_err(|_| ConfigError::InvalidValue("log_level".to_string(), self.log_level.clone()))
+    }
+}
+
+/// 10진수 혹은 `0x` 접두사가 붙은 16진수 인자를 해석한다.
+fn parse_arg<T: FromStr>(name: &str, value: &str) -> Result<T, ConfigError> {
+    let invalid = || ConfigError::InvalidValue(name.to_string(), value.to_string());
+    match value.strip_prefix("0x") {
+        Some(hex_value) => {
+            let parsed = u64::from_str_radix(hex_value, 16).map_err(|_| invalid())?;
+            T::from_str(parsed.to_string().as_str()).map_err(|_| invalid())
+        }
+        None => T::from_str(value).map_err(|_| invalid()),
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Help,
+    Io(String, String),
+    Toml(String),
+    MissingValue(String),
+    InvalidValue(String, String),
+    UnknownArgument(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Help => write!(f, "{}", USAGE),
+            ConfigError::Io(path, e) => write!(f, "could not read the config file '{}': {}", path, e),
+            ConfigError::Toml(e) => write!(f, "could not parse the config file: {}", e),
+            ConfigError::MissingValue(name) => write!(f, "'{}' requires a value", name),
+            ConfigError::InvalidValue(name, value) => write!(f, "invalid value '{}' for '{}'", value, name),
+            ConfigError::UnknownArgument(name) => write!(f, "unknown argument '{}'", name),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
@@ -0,0 +1,102 @@
+use basic_http::request::HttpRequest;
+use basic_http::response::HttpResponse;
+use basic_http::status::HttpStatusCode;
+use log::warn;
+use serde::Deserialize;
+
+/// 브라우저에서 직접 접속하는 dapp을 위한 CORS 설정
+/// # [Fields]
+/// - `allowed_origins: Vec<String>`    - 허용할 Origin 목록. `*`이면 모두 허용한다.
+/// - `allowed_methods: Vec<String>`    - preflight에 응답할 HTTP 메서드 목록
+/// - `allowed_headers: Vec<String>`    - 허용할 요청 헤더 목록. `*`이면 요청한 헤더를 그대로 허용한다.
+/// - `max_age: u64`                    - preflight 결과를 캐시할 시간(초)
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct CorsConfig {
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    pub max_age: u64,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec!["*".to_string()],
+            allowed_methods: vec!["POST".to_string(), "GET".to_string(), "OPTIONS".to_string()],
+            allowed_headers: vec!["Content-Type".to_string(), "Authorization".to_string()],
+            max_age: 86400,
+        }
+    }
+}
+
+impl CorsConfig {
+    /// 요청의 Origin에 대해 `Access-Control-Allow-Origin`에 넣을 값
+    /// 허용하지 않는 Origin이면 `None`
+    pub fn allow_origin(&self, origin: Option<&str>) -> Option<String> {
+        if self.allowed_origins.iter().any(|allowed| allowed == "*") {
+            return Some("*".to_string());
+        }
+        let origin = origin?;
+        self.allowed_origins.iter()
+            .find(|allowed| allowed.eq_ignore_ascii_case(origin))
+            .map(|_| origin.to_string())
+    }
+
+    fn allow_headers(&self, requested_headers: Option<&str>) -> String {
+        if self.allowed_headers.iter().any(|allowed| allowed == "*") {
+            if let Some(requested) = requested_headers {
+                return requested.to_string();
+            }
+        }
+        self.allowed_headers.join(", ")
+    }
+
+    /// 일반 응답에 붙일 CORS 헤더
+    pub fn response_headers(&self, origin: Option<&str>) -> Vec<(&'static str, String)> {
+        let mut headers = vec![];
+        if let Some(allow_origin) = self.allow_origin(origin) {
+            if allow_origin != "*" {
+                headers.push(("Vary", "Origin".to_string()));
+            }
+            headers.push(("Access-Control-Allow-Origin", allow_origin));
+        }
+        headers
+    }
+
+    /// preflight 응답에 붙일 CORS 헤더. 허용하지 않는 Origin이면 `None`
+    pub fn preflight_headers(&self, origin: Option<&str>, requested_headers: Option<&str>) -> Option<Vec<(&'static str, String)>> {
+        self.allow_origin(origin)?;
+        let mut headers = self.response_headers(origin);
+        headers.push(("Access-Control-Allow-Methods", self.allowed_methods.join(", ")));
+        headers.push(("Access-Control-Allow-Headers", self.allow_headers(requested_headers)));
+        headers.push(("Access-Control-Max-Age", self.max_age.to_string()));
+        Some(headers)
+    }
+
+    /// 일반 JSON-RPC 응답에 CORS 헤더를 붙인다.
+    pub fn apply(&self, request: &HttpRequest, response: &mut HttpResponse) {
+        for (name, value) in self.response_headers(request.header("Origin")).iter() {
+            response.set_header(name, value.as_str());
+        }
+    }
+
+    /// OPTIONS preflight 요청에 응답한다.
+    pub fn preflight(&self, request: &HttpRequest, mut response: HttpResponse) {
+        let origin = request.header("Origin");
+        match self.preflight_headers(origin, request.header("Access-Control-Request-Headers")) {
+            Some(headers) => {
+                response.set_code(HttpStatusCode::NoContent);
+                for (name, value) in headers.iter() {
+                    response.set_header(name, value.as_str());
+                }
+            }
+            None => {
+                warn!("[JSON_RPC] ip={} preflight from disallowed origin '{}'", request.peer_addr, origin.unwrap_or(""));
+                response.set_code(HttpStatusCode::Forbidden);
+            }
+        }
+        response.set_data("");
+        response.send();
+    }
+}
@@ -0,0 +1,138 @@
+use crypto::hash::keccak256;
+use ethereum_types::{Address, H256, U256, U64};
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+use serde::Serialize;
+use web3_signer::error::RecoveryError;
+use crate::access_list_transaction::AccessListItem;
+use crate::bytes::Bytes;
+use crate::raw_transaction::recover_sender;
+use crate::typed_transaction::{append_to, decode_to, h256_from_u256, with_tx_type, DYNAMIC_FEE_TX_TYPE};
+
+/// EIP-1559 (type 2) 트랜잭션
+/// `0x02 || rlp([chainId, nonce, maxPriorityFeePerGas, maxFeePerGas, gasLimit, to, value, data, accessList, yParity, r, s])`
+/// 최신 MetaMask는 기본으로 이 형식에 서명한다.
+#[derive(Serialize, Clone, Debug)]
+pub struct DynamicFeeTransaction {
+    #[serde(rename="chainId")]
+    pub chain_id: U64,
+    pub nonce: U64,
+    #[serde(rename="maxPriorityFeePerGas")]
+    pub max_priority_fee_per_gas: U256,
+    #[serde(rename="maxFeePerGas")]
+    pub max_fee_per_gas: U256,
+    pub gas: U64,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Bytes,
+    #[serde(rename="accessList")]
+    pub access_list: Vec<AccessListItem>,
+    #[serde(rename="yParity")]
+    pub y_parity: U64,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl DynamicFeeTransaction {
+    /// 서명할 때 쓰는 해시. `keccak256(0x02 || rlp(서명을 뺀 필드))`
+    pub fn signing_hash(&self) -> H256 {
+        let mut stream = RlpStream::new();
+        stream.begin_list(9);
+        self.rlp_append_fields(&mut stream);
+        keccak256(with_tx_type(DYNAMIC_FEE_TX_TYPE, stream.as_raw()).as_slice())
+    }
+
+    pub fn sender(&self) -> Result<Address, RecoveryError> {
+        recover_sender(self.signing_hash(), self.y_parity.as_u64() as i32, h256_from_u256(&self.r), h256_from_u256(&self.s))
+    }
+
+    fn rlp_append_fields(&self, s: &mut RlpStream) {
+        s.append(&self.chain_id);
+        s.append(&self.nonce);
+        s.append(&self.max_priority_fee_per_gas);
+        s.append(&self.max_fee_per_gas);
+        s.append(&self.gas);
+        append_to(s, &self.to);
+        s.append(&self.value);
+        s.append(&self.input.0);
+        s.append_list(&self.access_list);
+    }
+}
+
+/// type 바이트를 뺀 payload
+impl Encodable for DynamicFeeTransaction {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(12);
+        self.rlp_append_fields(s);
+        s.append(&self.y_parity);
+        s.append(&self.r);
+        s.append(&self.s);
+    }
+}
+
+impl Decodable for DynamicFeeTransaction {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 12 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            nonce: rlp.val_at(1)?,
+            max_priority_fee_per_gas: rlp.val_at(2)?,
+            max_fee_per_gas: rlp.val_at(3)?,
+            gas: rlp.val_at(4)?,
+            to: decode_to(&rlp.at(5)?)?,
+            value: rlp.val_at(6)?,
+            input: Bytes(rlp.val_at(7)?),
+            access_list: rlp.list_at(8)?,
+            y_parity: rlp.val_at(9)?,
+            r: rlp.val_at(10)?,
+            s: rlp.val_at(11)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use ethereum_types::{Address, H256, U256};
+    use crate::typed_transaction::TypedTransaction;
+
+    /// chain id 1, nonce 0, maxPriorityFeePerGas 1 gwei, maxFeePerGas 30 gwei. 개인키 0x00...01로 서명
+    const RAW: &str = "02f86b0180843b9aca008506fc23ac008252089435353535353535353535353535353535353535350180c080a0f15e41afc5e28cf1670f07bcbe3e12ef3865f082b72282d6be03a3b72c21558da03ace5a7feafd98eaf92f144a7e4505cb5d6cf925d55b526a00f67121d8c2a3c9";
+
+    #[test]
+    fn signed_dynamic_fee_transaction() {
+        let raw = hex::decode(RAW).unwrap();
+        let tx = TypedTransaction::decode(raw.as_slice()).unwrap();
+        assert!(matches!(tx, TypedTransaction::DynamicFee(_)));
+        assert_eq!(tx.chain_id(), Some(1));
+        assert_eq!(tx.signing_hash(), H256::from_str("ff218eb3a1433919c365bdff196948ef9e9fd397d67897a4df68a5947942814c").unwrap());
+        assert_eq!(tx.hash(raw.as_slice()), H256::from_str("232b930179d09eb0ae1a913ba0d630b4fce7960271d5a7262eaf5fcaa0b99b6c").unwrap());
+        assert_eq!(tx.sender().unwrap(), Address::from_str("7e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap());
+        assert_eq!(tx.encode(), raw);
+    }
+
+    #[test]
+    fn effective_gas_price() {
+        let raw = hex::decode(RAW).unwrap();
+        let tx = TypedTransaction::decode(raw.as_slice()).unwrap();
+        let gwei = U256::from(1_000_000_000u64);
+        assert_eq!(tx.effective_gas_price(Some(gwei * 10)), gwei * 11);
+        assert_eq!(tx.effective_gas_price(Some(gwei * 40)), gwei * 30);
+        assert_eq!(tx.effective_gas_price(None), gwei * 30);
+    }
+
+    #[test]
+    fn fee_cap() {
+        let raw = hex::decode(RAW).unwrap();
+        let mut tx = TypedTransaction::decode(raw.as_slice()).unwrap();
+        let gwei = U256::from(1_000_000_000u64);
+        assert!(tx.check_fee_cap(Some(gwei * 30)).is_ok());
+        assert!(tx.check_fee_cap(Some(gwei * 31)).unwrap_err().starts_with("max fee per gas less than block base fee"));
+        if let TypedTransaction::DynamicFee(dtx) = &mut tx {
+            dtx.max_priority_fee_per_gas = gwei * 31;
+        }
+        assert!(tx.check_fee_cap(None).unwrap_err().starts_with("max priority fee per gas higher than max fee per gas"));
+        assert!(tx.validate().is_err());
+    }
+}
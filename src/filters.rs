@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use ethereum_types::{H256, U256};
+use serde::Serialize;
+use crate::blockchain::Blockchain;
+use crate::logs::{LogFilter, LogStore};
+use crate::receipt::Log;
+use crate::response::RpcError;
+
+pub const DEFAULT_FILTER_TIMEOUT: u64 = 300;
+pub const DEFAULT_MAX_FILTERS: usize = 1000;
+
+/// 필터 종류
+/// - `Logs`                - eth_newFilter. 조건에 맞는 새 로그
+/// - `Blocks`              - eth_newBlockFilter. 새 블록 해시
+/// - `PendingTransactions` - eth_newPendingTransactionFilter. 새로 받은 트랜잭션 해시
+pub enum FilterKind {
+    Logs(LogFilter),
+    Blocks,
+    PendingTransactions,
+}
+
+/// eth_getFilterChanges의 결과. 로그 필터는 로그, 나머지는 해시 목록이다.
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+pub enum FilterChanges {
+    Hashes(Vec<H256>),
+    Logs(Vec<Log>),
+}
+
+/// 설치된 필터
+/// # [Fields]
+/// - `kind: FilterKind`
+/// - `next_block: u64`             - 아직 돌려주지 않은 첫 블록 번호 (로그, 블록 필터)
+/// - `pending: Vec<H256>`          - 아직 돌려주지 않은 트랜잭션 해시 (트랜잭션 필터)
+/// - `last_poll: Instant`          - 마지막으로 조회한 시각. `timeout`이 지나면 지운다.
+struct Filter {
+    kind: FilterKind,
+    next_block: u64,
+    pending: Vec<H256>,
+    last_poll: Instant,
+}
+
+struct FilterInner {
+    filters: HashMap<U256, Filter>,
+    next_id: U256,
+}
+
+/// eth_newFilter 계열 메서드가 만든 필터를 id로 보관한다.
+/// 조회할 때마다 오래 조회하지 않은 필터를 지우고, 지워졌거나 없는 id는 "filter not found" 에러를 돌려준다.
+pub struct FilterManager {
+    timeout: Duration,
+    max_filters: usize,
+    inner: Mutex<FilterInner>,
+}
+
+impl FilterManager {
+    pub fn new(timeout: Duration, max_filters: usize) -> Self {
+        Self {
+            timeout,
+            max_filters,
+            inner: Mutex::new(FilterInner { filters: HashMap::new(), next_id: U256::one() }),
+        }
+    }
+
+    /// 필터를 설치하고 id를 돌려준다. 로그, 블록 필터는 다음 블록부터 본다.
+    pub fn install(&self, kind: FilterKind, chain: &Blockchain) -> Result<U256, RpcError> {
+        let mut inner = self.lock();
+        if inner.filters.len() >= self.max_filters {
+            return Err(RpcError::limit_exceeded(format!("too many filters (max {})", self.max_filters).as_str()));
+        }
+        let id = inner.next_id;
+        inner.next_id = id + 1;
+        inner.filters.insert(id, Filter {
+            kind,
+            next_block: chain.number() + 1,
+            pending: vec![],
+            last_poll: Instant::now(),
+        });
+        Ok(id)
+    }
+
+    /// 지웠으면 `true`, 없는 id이면 `false`
+    pub fn uninstall(&self, id: &U256) -> bool {
+        self.lock().filters.remove(id).is_some()
+    }
+
+    /// 받은 트랜잭션을 트랜잭션 필터에 쌓아 둔다.
+    pub fn notify_pending_transaction(&self, hash: H256) {
+        for filter in self.lock().filters.values_mut() {
+            if let FilterKind::PendingTransactions = filter.kind {
+                filter.pending.push(hash);
+            }
+        }
+    }
+
+    /// 지난 조회 이후 바뀐 내용
+    pub fn changes(&self, id: &U256, chain: &Blockchain, logs: &LogStore) -> Result<FilterChanges, RpcError> {
+        let mut inner = self.lock();
+        let filter = inner.filters.get_mut(id).ok_or_else(filter_not_found)?;
+        filter.last_poll = Instant::now();
+        // 조회하는 동안 블록이 만들어져도 범위와 next_block이 어긋나지 않도록 최신 블록 번호를 한 번만 읽는다.
+        let head = chain.number();
+        let changes = match &filter.kind {
+            FilterKind::Logs(log_filter) => {
+                let (from, to) = log_filter.bounds(chain, head)?;
+                let from = from.max(filter.next_block);
+                FilterChanges::Logs(match from <= to {
+                    true => logs.logs(from..=to, log_filter),
+                    false => vec![],
+                })
+            }
+            FilterKind::Blocks => FilterChanges::Hashes((filter.next_block..=head)
+                .filter_map(|number| chain.block_by_number(number))
+                .map(|block| block.hash)
+                .collect()),
+            FilterKind::PendingTransactions => FilterChanges::Hashes(std::mem::take(&mut filter.pending)),
+        };
+        filter.next_block = filter.next_block.max(head + 1);
+        Ok(changes)
+    }
+
+    /// 로그 필터와 맞는 로그 전체. 로그 필터가 아니면 "filter not found" 에러
+    pub fn logs(&self, id: &U256, chain: &Blockchain, logs: &LogStore, max_block_range: u64) -> Result<Vec<Log>, RpcError> {
+        let mut inner = self.lock();
+        let filter = inner.filters.get_mut(id).ok_or_else(filter_not_found)?;
+        filter.last_poll = Instant::now();
+        match &filter.kind {
+            FilterKind::Logs(log_filter) => Ok(logs.logs(log_filter.block_range(chain, max_block_range)?, log_filter)),
+            _ => Err(filter_not_found()),
+        }
+    }
+
+    /// 잠그면서 `timeout` 동안 조회하지 않은 필터를 지운다.
+    fn lock(&self) -> std::sync::MutexGuard<'_, FilterInner> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let timeout = self.timeout;
+        inner.filters.retain(|_, filter| filter.last_poll.elapsed() < timeout);
+        inner
+    }
+}
+
+fn filter_not_found() -> RpcError {
+    RpcError::invalid_input("filter not found")
+}
@@ -1,257 +1,161 @@
-use std::io::Write;
 use std::sync::Mutex;
+use std::time::Instant;
 use basic_http::request::HttpRequest;
 use basic_http::response::HttpResponse;
 use basic_http::status::HttpStatusCode;
 use hub_channel::channel::Channel;
 use log::{info, warn};
-use serde_json::{Error, Value};
-use crate::consts::RPC_VERSION;
-use crate::methods::eth_block_number::EthBlockNumber;
-use crate::methods::eth_call::EthCall;
-use crate::methods::eth_chain_id::EthChainId;
-use crate::methods::eth_estimate_gas::EthEstimateGas;
-use crate::methods::eth_gas_price::EthGasPrice;
-use crate::methods::eth_get_balance::EthGetBalance;
-use crate::methods::eth_get_block_by_number::EthGetBlockByNumber;
-use crate::methods::eth_get_transaction_count::EthGetTransactionCount;
-use crate::methods::eth_protocol_version::EthProtocolVersion;
-use crate::methods::eth_send_raw_transaction::EthSendRawTransaction;
-use crate::methods::net_version::NetVersion;
-use crate::methods::RpcCall;
-use crate::methods::web3::{Web3ClientVersion, Web3Sha3};
-use crate::request::{CommonRpcEmptyRequest, CommonRpcStringsRequest, new_common_rpc_empty_request, new_common_rpc_object_request, new_common_rpc_strings_request, RequestIdtype, RpcEmptyRequest, RpcEmptyRequestWithUuid, RpcStringsRequest, RpcStringsRequestWithUuid};
-use crate::utils::fileutil::load_file;
-use crate::utils::timeutil::str_utc_time_now;
+use serde_json::Value;
+use crate::methods::RpcContext;
+use crate::node::{Node, ReceivedRequest};
+use crate::recorder::RecordedCall;
+use crate::request::RpcId;
+use crate::response::{new_json_rpc_error_response, RpcError, RpcResponse};
 
 pub fn rpc_handler(request: HttpRequest, mut response: HttpResponse, ch: &Mutex<Channel>) {
+    let node = Node::global();
+    let cors = &node.config.cors;
+    if request.method().eq_ignore_ascii_case("OPTIONS") {
+        cors.preflight(&request, response);
+        return;
+    }
+    cors.apply(&request, &mut response);
+    let _in_flight = match node.lifecycle.enter() {
+        Some(in_flight) => in_flight,
+        None => {
+            send_error(&mut response, HttpStatusCode::ServiceUnavailable, &RpcId::Null, &RpcError::resource_unavailable("node is shutting down"));
+            return;
+        }
+    };
+
     let data = request.body().data();
     if data.len() == 0 {
         warn!("[JSON_RPC] data length is zero. Are you trying to connect via Firefox Metamask?");
-        response.set_code(HttpStatusCode::BadRequest);
-        response.send(); // 응답을 주지 않으면 반항심 때문인지 계속 보낸다..
+        // 응답을 주지 않으면 반항심 때문인지 계속 보낸다..
+        send_error(&mut response, HttpStatusCode::BadRequest, &RpcId::Null, &RpcError::invalid_request("empty body"));
         return;
     }
 
-    let a: Result<Value, serde_json::Error> = serde_json::from_str(data);
-    match a {
-        Ok(_) => { /* 정상적인 경우에는 실행할 내용이 없다. */ }
-        Err(_) => {
-            warn!("[JSON_RPC] EOF while parsing a string");
-            return;
-        }
+    let peer_addr = request.peer_addr.to_string();
+    let ctx = RpcContext { node, channel: Some(ch), subscriber: None };
+    let data = handle_rpc_payload(data, &peer_addr, &ctx);
+    response.set_code(HttpStatusCode::Ok);
+    response.set_data(data.as_str());
+    response.send();
+}
+
+/// 전송 계층과 상관없이 JSON-RPC 본문(단일 요청 혹은 배치)을 처리한다.
+/// notification만 있는 요청에는 응답할 내용이 없으므로 빈 문자열을 돌려준다.
+pub fn handle_rpc_payload(data: &str, peer_addr: &str, ctx: &RpcContext) -> String {
+    let rpc_payload: Value = match serde_json::from_str(data) {
+        Ok(rpc_payload) => rpc_payload,
+        Err(e) => {
+            warn!("[JSON_RPC] ip={} could not parse the message: {}", peer_addr, e);
+            return new_json_rpc_error_response(&RpcId::Null, &RpcError::parse_error());
+        }
+    };
+    handle_rpc_value(rpc_payload, peer_addr, ctx)
+}
+
+/// 이미 해석한 JSON-RPC 본문을 처리한다. 스트림에서 JSON 값을 하나씩 읽는 전송 계층(IPC)이 쓴다.
+pub fn handle_rpc_value(rpc_payload: Value, peer_addr: &str, ctx: &RpcContext) -> String {
+    match rpc_payload {
+        Value::Array(rpc_objects) => handle_rpc_batch(&rpc_objects, peer_addr, ctx),
+        rpc_object => handle_rpc_object(&rpc_object, peer_addr, ctx).unwrap_or_default(),
     }
-    let rpc_object: Value = serde_json::from_str(data).unwrap();
-    let opt_rpc_method = rpc_object.get("method");
-    let opt_rpc_id = rpc_object.get("id");
-    let opt_rpc_params = rpc_object.get("params");
+}
 
-    // 셋 중 하나라도 없다면 정상적인 RPC 콜이 아니다.
-    if opt_rpc_method.is_none() && opt_rpc_id.is_none() && opt_rpc_params.is_none() {
-        warn!("[JSON_RPC] broken message received");
-        return;
+/// 배치 요청을 처리한다.
+/// 각 요청을 순서대로 처리하여 응답을 같은 순서의 배열로 돌려준다.
+/// id가 없는 요청(notification)은 응답 배열에 포함하지 않는다.
+fn handle_rpc_batch(rpc_objects: &Vec<Value>, peer_addr: &str, ctx: &RpcContext) -> String {
+    if rpc_objects.is_empty() {
+        warn!("[JSON_RPC] ip={} empty batch received", peer_addr);
+        return new_json_rpc_error_response(&RpcId::Null, &RpcError::invalid_request("empty batch"));
     }
+    info!("[JSON_RPC] ip={} batch size={}", peer_addr, rpc_objects.len());
 
-    let mut rpc_id_type: RequestIdtype;
-    match opt_rpc_id.unwrap().as_u64() {
-        None => rpc_id_type = RequestIdtype::Uuid,
-        Some(u64_rpc_id) => rpc_id_type = RequestIdtype::U64,
+    let responses: Vec<String> = rpc_objects.iter()
+        .filter_map(|rpc_object| handle_rpc_object(rpc_object, peer_addr, ctx))
+        .collect();
+    match responses.len() {
+        0 => "".to_string(),
+        _ => format!("[{}]", responses.join(",")),
     }
-    let rpc_method = opt_rpc_method.unwrap().as_str().unwrap();
-    let rpc_params = opt_rpc_params.unwrap().as_array().unwrap();
+}
 
-    info!("[JSON_RPC] ip={} method='{}'", request.peer_addr, rpc_method);
+/// 단일 요청 객체를 처리하고 JSON 응답 문자열을 돌려준다.
+/// id가 없는 요청은 notification이므로 실행만 하고 응답하지 않는다. (`None`)
+fn handle_rpc_object(rpc_object: &Value, peer_addr: &str, ctx: &RpcContext) -> Option<String> {
+    let is_notification = rpc_object.is_object() && rpc_object.get("id").is_none();
+    let data = execute_rpc_object(rpc_object, peer_addr, ctx);
+    if is_notification {
+        info!("[JSON_RPC] ip={} notification handled, response dropped: {}", peer_addr, data);
+        return None;
+    }
+    Some(data)
+}
 
-    match rpc_method {
-        "web3_clientVersion" => {
-            let rpc_request = new_common_rpc_empty_request(rpc_id_type, opt_rpc_id, rpc_method);
-            let data = Web3ClientVersion::from(rpc_request).receive(ch);
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-        }
-        "web3_sha3" => {
-            const METHOD: &str = "web3_sha3";
-            let rpc_request = new_common_rpc_strings_request(rpc_id_type, opt_rpc_id, rpc_method, rpc_params);
-            // let rpc_request = RpcStringsRequest::new(rpc_id, RPC_VERSION, "web3_sha3", rpc_params);
-            let data = Web3Sha3::from(rpc_request).receive(ch);
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-        }
-        "net_version" => {
-            let rpc_request = new_common_rpc_empty_request(rpc_id_type, opt_rpc_id, rpc_method);
-            // let rpc_request = RpcEmptyRequest::new(rpc_id, RPC_VERSION, "net_version");
-            let data = NetVersion::from(rpc_request).receive(ch);
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-        }
-        "net_peerCount" => { /* unsupported method */ }
-        "net_listening" => { /* unsupported method */}
-        "eth_protocolVersion" => {
-            let rpc_request = new_common_rpc_empty_request(rpc_id_type, opt_rpc_id, rpc_method);
-            // let rpc_request = RpcEmptyRequest::new(rpc_id, RPC_VERSION, "eth_protocolVersion");
-            let data = EthProtocolVersion::from(rpc_request).receive(ch);
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-        }
-        "eth_syncing" => { /* unsupported method */ }
-        "eth_coinbase" => { /* unsupported method */ }
-        "eth_mining" => { /* unsupported method */ }
-        "eth_hashrate" => { /* unsupported method */ }
-        "eth_gasPrice" => {
-            let rpc_request = new_common_rpc_empty_request(rpc_id_type, opt_rpc_id, rpc_method);
-            // let rpc_request = RpcEmptyRequest::new(rpc_id, RPC_VERSION, "eth_gasPrice");
-            let data = EthGasPrice::from(rpc_request).receive(ch);
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-        }
-        "eth_accounts" => {
-            let channel = ch.lock().unwrap();
-            // std::mem::drop(channel);
-        }
-        "eth_blockNumber" => {
-            // let request = RpcEmptyRequest::new(rpc_id, RPC_VERSION, "eth_blockNumber");
-            let rpc_request = new_common_rpc_empty_request(rpc_id_type, opt_rpc_id, "eth_blockNumber");
-            let data = EthBlockNumber::from(rpc_request).receive(ch);
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-        }
-        "eth_getBalance" => {
-            let rpc_request =
-                new_common_rpc_strings_request(rpc_id_type, opt_rpc_id, rpc_method, rpc_params);
-            // let rpc_request = RpcStringsRequest::new(rpc_id, RPC_VERSION, "eth_getBalance", rpc_params);
-            let data = EthGetBalance::from(rpc_request).receive(ch);
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-        }
-        "eth_getStorageAt" => {}
-        "eth_getTransactionCount" => {
-            let rpc_request = new_common_rpc_strings_request(rpc_id_type, opt_rpc_id, rpc_method, rpc_params);
-            let data = EthGetTransactionCount::from(rpc_request).receive(ch);
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-        }
-        "eth_getBlockTransactionCountByHash" => {}
-        "eth_getBlockTransactionCountByNumber" => {}
-        "eth_getUncleCountByBlockHash" => {}
-        "eth_getUncleCountByBlockNumber" => {}
-        "eth_getCode" => {}
-        "eth_sign" => {}
-        "eth_signTransaction" => {}
-        "eth_sendTransaction" => {}
-        "eth_sendRawTransaction" => {
-            let rpc_request = new_common_rpc_strings_request(rpc_id_type, opt_rpc_id, rpc_method, rpc_params);
-            let mut str_params: &str;
-            for param in rpc_request.params().iter() {
-                let compatible_filename = format!("./{}.txt", str_utc_time_now().replace(":", "_"));
-                info!("filename: {}", compatible_filename);
-                let mut f = load_file(compatible_filename.as_str()).unwrap();
-                let str_param = format!("{}", param);
-                f.write(str_param.as_bytes());
-            }
-            let data = EthSendRawTransaction::from(rpc_request).receive(ch);
-            println!(r#"{}"#, data.as_str());
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-        }
-        "eth_call" => {
-            let rpc_request = new_common_rpc_strings_request(rpc_id_type, opt_rpc_id, rpc_method, rpc_params);
-            let data = EthCall::from(rpc_request).receive(ch);
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-            // println!("{}", rpc_request.params().len());
-            // for param in rpc_request.params().iter() {
-            //     println!("{}", param);
-            // }
-        }
-        "eth_estimateGas" => {
-            let rpc_request = new_common_rpc_empty_request(rpc_id_type, opt_rpc_id, rpc_method);
-            // let rpc_request = RpcEmptyRequest::new(rpc_id, RPC_VERSION, "eth_estimateGas");
-            let data = EthEstimateGas::from(rpc_request).receive(ch);
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-        }
-        "eth_getBlockByHash" => {}
-        "eth_getBlockByNumber" => {
-            let rpc_request = new_common_rpc_strings_request(rpc_id_type, opt_rpc_id, rpc_method, rpc_params);
-            let data = EthGetBlockByNumber::from(rpc_request).receive(ch);
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-        }
-        "eth_getTransactionByHash" => {}
-        "eth_getTransactionByBlockHashAndIndex" => {}
-        "eth_getTransactionByBlockNumberAndIndex" => {}
-        "eth_getTransactionReceipt" => {}
-        "eth_getUncleByBlockHashAndIndex" => {}
-        "eth_getUncleByBlockNumberAndIndex" => {}
-        "eth_getCompilers" => {}
-        "eth_compileLLL" => {}
-        "eth_compileSolidity" => {}
-        "eth_compileSerpent" => {}
-        "eth_newFilter" => {}
-        "eth_newBlockFilter" => {}
-        "eth_newPendingTransactionFilter" => {}
-        "eth_uninstallFilter" => {}
-        "eth_getFilterChanges" => {}
-        "eth_getFilterLogs" => {}
-        "eth_getLogs" => {}
-        "eth_getWork" => {}
-        "eth_submitWork" => {}
-        "eth_submitHashrate" => {}
-        "db_putString" => {}
-        "db_getString" => {}
-        "db_putHex" => {}
-        "db_getHex" => {}
-        "shh_post" => {}
-        "shh_version" => {}
-        "shh_newIdentity" => {}
-        "shh_hasIdentity" => {}
-        "shh_newGroup" => {}
-        "shh_addToGroup" => {}
-        "shh_newFilter" => {}
-        "shh_uninstallFilter" => {}
-        "shh_getFilterChanges" => {}
-        "shh_getMessages" => {}
-        "eth_chainId" => {
-            let rpc_request = new_common_rpc_empty_request(rpc_id_type, opt_rpc_id, rpc_method);
-            let data = EthChainId::from(rpc_request).receive(ch);
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-        }
-        // "eth_chainId" => {
-        //     const METHOD: &str = "eth_chainId";
-        //     let rpc_request: Box<dyn CommonRpcEmptyRequest>;
-        //     match rpc_id_type {
-        //         RequestIdtype::Uuid => {
-        //             let rpc_id = opt_rpc_id.unwrap().as_str().unwrap();
-        //             rpc_request = Box::new(RpcEmptyRequestWithUuid::new(rpc_id, RPC_VERSION, METHOD));
-        //         }
-        //         RequestIdtype::U64 => {
-        //             let rpc_id = opt_rpc_id.unwrap().as_u64().unwrap();
-        //             rpc_request = Box::new(RpcEmptyRequest::new(&rpc_id, RPC_VERSION, METHOD));
-        //         }
-        //     }
-        //     // let rpc_request = RpcEmptyRequest::new(rpc_id, RPC_VERSION, "net_version");
-        //     let data = EthChainId::from(rpc_request).receive(ch);
-        //     response.set_code(HttpStatusCode::Ok);
-        //     response.set_data(data.as_str());
-        // }
-        "debugRawTransaction" => {
-            let rpc_request = new_common_rpc_strings_request(rpc_id_type, opt_rpc_id, rpc_method, rpc_params);
-            let data = crate::methods::debug::debug_raw_transaction::DebugRawTransaction::from(rpc_request).receive(ch);
-            response.set_code(HttpStatusCode::Ok);
-            response.set_data(data.as_str());
-        }
-        &_ => {
-            println!("[JSON_RPC] could not handle the method '{}'", rpc_method);
-            response.set_code(HttpStatusCode::NotFound);
-            response.set_data("");
+fn execute_rpc_object(rpc_object: &Value, peer_addr: &str, ctx: &RpcContext) -> String {
+    if !rpc_object.is_object() {
+        warn!("[JSON_RPC] broken message received");
+        return new_json_rpc_error_response(&RpcId::Null, &RpcError::invalid_request("not a JSON object"));
+    }
+    let opt_rpc_id = match rpc_object.get("id") {
+        None => Some(RpcId::Null),
+        Some(id) => RpcId::from_value(id),
+    };
+    let rpc_id = match opt_rpc_id {
+        Some(rpc_id) => rpc_id,
+        None => {
+            warn!("[JSON_RPC] ip={} unsupported id '{:?}'", peer_addr, rpc_object.get("id"));
+            return new_json_rpc_error_response(&RpcId::Null, &RpcError::invalid_request("id must be a number, a string or null"));
+        }
+    };
+    let rpc_method = match rpc_object.get("method").and_then(|method| method.as_str()) {
+        Some(rpc_method) => rpc_method,
+        None => {
+            warn!("[JSON_RPC] ip={} method is missing", peer_addr);
+            return new_json_rpc_error_response(&rpc_id, &RpcError::invalid_request("method must be a string"));
+        }
+    };
+    let rpc_params = match rpc_object.get("params") {
+        None | Some(Value::Null) => Value::Array(vec![]),
+        Some(Value::Array(params)) => Value::Array(params.clone()),
+        Some(_) => {
+            return new_json_rpc_error_response(&rpc_id, &RpcError::invalid_params("params must be an array"));
+        }
+    };
+
+    info!("[JSON_RPC] ip={} method='{}'", peer_addr, rpc_method);
+    ctx.node.history.record_request(ReceivedRequest {
+        id: rpc_object.get("id").map(|_| rpc_id.clone()),
+        method: rpc_method.to_string(),
+        params: rpc_params.clone(),
+        peer_addr: peer_addr.to_string(),
+    });
+
+    let received_at = chrono::Utc::now();
+    let started = Instant::now();
+    let result = ctx.node.registry.call(rpc_method, rpc_params.clone(), ctx);
+    if let Some(recorder) = &ctx.node.recorder {
+        let call = RecordedCall::new(received_at, peer_addr, rpc_object.get("id"), rpc_method, &rpc_params, &result, started.elapsed());
+        if let Err(e) = recorder.record(&call) {
+            warn!("[RECORDER] could not write to {}: {}", recorder.path().display(), e);
         }
     }
-    match response.get_data().len() {
-        0 => {
-            warn!("[JSON_RPC] ip={} could not handled '{}' due to internal error", request.peer_addr, rpc_method);
+    match result {
+        Ok(result) => RpcResponse::new(&rpc_id, result).to_json(),
+        Err(e) => {
+            warn!("[JSON_RPC] ip={} method='{}' error={} {}", peer_addr, rpc_method, e.code, e.message);
+            new_json_rpc_error_response(&rpc_id, &e)
         }
-        _ => {}
     }
+}
+
+fn send_error(response: &mut HttpResponse, code: HttpStatusCode, id: &RpcId, error: &RpcError) {
+    let data = new_json_rpc_error_response(id, error);
+    response.set_code(code);
+    response.set_data(data.as_str());
     response.send();
-}
\ No newline at end of file
+}
@@ -0,0 +1,58 @@
+use std::sync::Mutex;
+use basic_http::request::HttpRequest;
+use basic_http::response::HttpResponse;
+use basic_http::status::HttpStatusCode;
+use hub_channel::channel::Channel;
+use serde_json::json;
+use crate::node::Node;
+
+/// `GET /health`
+/// 프로세스가 살아 있으면 항상 200을 돌려준다.
+pub fn health_handler(request: HttpRequest, response: HttpResponse, _ch: &Mutex<Channel>) {
+    let (_, data) = health(Node::global());
+    send(&request, response, HttpStatusCode::Ok, data.as_str());
+}
+
+/// `GET /ready`
+/// 요청을 받을 수 있으면 200, 종료 중이면 503을 돌려준다.
+pub fn ready_handler(request: HttpRequest, response: HttpResponse, _ch: &Mutex<Channel>) {
+    let (ready, data) = ready(Node::global());
+    let code = match ready {
+        true => HttpStatusCode::Ok,
+        false => HttpStatusCode::ServiceUnavailable,
+    };
+    send(&request, response, code, data.as_str());
+}
+
+/// 전송 계층과 상관없는 `/health` 응답. (상태, JSON 본문)
+pub fn health(node: &Node) -> (bool, String) {
+    let data = json!({
+        "status": "ok",
+        "shuttingDown": node.lifecycle.is_shutting_down(),
+        "inFlight": node.lifecycle.in_flight(),
+    });
+    (true, data.to_string())
+}
+
+/// 전송 계층과 상관없는 `/ready` 응답. (준비 여부, JSON 본문)
+pub fn ready(node: &Node) -> (bool, String) {
+    let ready = !node.lifecycle.is_shutting_down();
+    let data = json!({
+        "ready": ready,
+        "chainId": format!("0x{:x}", node.config.chain_id),
+        "clientVersion": node.config.client_version,
+    });
+    (ready, data.to_string())
+}
+
+fn send(request: &HttpRequest, mut response: HttpResponse, code: HttpStatusCode, data: &str) {
+    if !request.method().eq_ignore_ascii_case("GET") {
+        response.set_code(HttpStatusCode::MethodNotAllowed);
+        response.send();
+        return;
+    }
+    response.set_header("Content-Type", "application/json");
+    response.set_code(code);
+    response.set_data(data);
+    response.send();
+}
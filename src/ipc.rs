@@ -0,0 +1,67 @@
+use std::io::{BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::sync::{Arc, Mutex};
+use log::{info, warn};
+use serde_json::Value;
+use crate::handler::handle_rpc_value;
+use crate::methods::RpcContext;
+use crate::node::Node;
+use crate::request::RpcId;
+use crate::response::{new_json_rpc_error_response, RpcError};
+
+/// IPC 연결 하나를 끊길 때까지 처리한다.
+/// 요청은 줄바꿈으로 나누었거나 그냥 이어 붙인 JSON 값이고, 응답과 eth_subscribe 알림은 한 줄에 하나씩 쓴다.
+pub fn serve(stream: UnixStream, peer_addr: &str, node: &Node) {
+    let writer = match stream.try_clone() {
+        Ok(writer) => Arc::new(Mutex::new(writer)),
+        Err(e) => {
+            warn!("[IPC] ip={} could not clone the stream: {}", peer_addr, e);
+            return;
+        }
+    };
+    let (subscriber, notifications) = node.subscriptions.connect();
+    let notification_writer = writer.clone();
+    let forwarder = std::thread::spawn(move || {
+        for notification in notifications {
+            if write_line(&notification_writer, notification.as_str()).is_err() {
+                break;
+            }
+        }
+    });
+    info!("[IPC] ip={} connected", peer_addr);
+
+    let rpc_payloads = serde_json::Deserializer::from_reader(BufReader::new(stream)).into_iter::<Value>();
+    for rpc_payload in rpc_payloads {
+        let response = match rpc_payload {
+            Ok(rpc_payload) => match node.lifecycle.enter() {
+                Some(_in_flight) => {
+                    let ctx = RpcContext { node, channel: None, subscriber: Some(&subscriber) };
+                    handle_rpc_value(rpc_payload, peer_addr, &ctx)
+                }
+                None => new_json_rpc_error_response(&RpcId::Null, &RpcError::resource_unavailable("node is shutting down")),
+            },
+            Err(e) if e.is_eof() || e.is_io() => break,
+            Err(e) => {
+                // 스트림 중간에서 깨진 JSON은 다음 요청의 시작을 알 수 없으므로 연결을 끊는다.
+                warn!("[JSON_RPC] ip={} could not parse the message: {}", peer_addr, e);
+                let _ = write_line(&writer, new_json_rpc_error_response(&RpcId::Null, &RpcError::parse_error()).as_str());
+                break;
+            }
+        };
+        if !response.is_empty() && write_line(&writer, response.as_str()).is_err() {
+            break;
+        }
+    }
+
+    let removed = node.subscriptions.disconnect(subscriber.connection_id);
+    drop(subscriber);
+    let _ = forwarder.join();
+    info!("[IPC] ip={} disconnected, {} subscription(s) removed", peer_addr, removed);
+}
+
+fn write_line(writer: &Mutex<UnixStream>, data: &str) -> std::io::Result<()> {
+    let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+    writer.write_all(data.as_bytes())?;
+    writer.write_all(b"\n")?;
+    writer.flush()
+}
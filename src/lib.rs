@@ -0,0 +1,49 @@
+//! Metamask sign checker
+//! 지갑이 보내는 JSON-RPC 요청과 서명된 트랜잭션을 확인하기 위한 목 이더리움 노드
+//! 바이너리로 실행하거나, 테스트 코드에서 [`MockServer`]로 띄워서 쓴다.
+
+pub mod access_list_transaction;
+pub mod account;
+pub mod blob_transaction;
+pub mod block;
+pub mod blockchain;
+pub mod block_tag;
+pub mod bytes;
+pub mod config;
+pub mod consts;
+pub mod dynamic_fee_transaction;
+pub mod filters;
+pub mod cors;
+pub mod handler;
+pub mod health;
+#[cfg(unix)]
+pub mod ipc;
+pub mod lifecycle;
+pub mod listener;
+pub mod logging;
+pub mod logs;
+pub mod methods;
+pub mod miner;
+pub mod node;
+pub mod raw_transaction;
+pub mod receipt;
+pub mod recorder;
+pub mod request;
+pub mod response;
+pub mod server;
+pub mod set_code_transaction;
+pub mod signature_report;
+pub mod signer;
+pub mod subscriptions;
+pub mod transaction;
+pub mod transaction_pool;
+pub mod trie;
+pub mod typed_transaction;
+pub mod utils;
+pub mod websocket;
+pub mod world_state;
+
+pub use crate::config::Config;
+pub use crate::methods::registry::MethodRegistry;
+pub use crate::node::Node;
+pub use crate::server::{MockServer, MockServerBuilder};
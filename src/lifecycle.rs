@@ -0,0 +1,58 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+const DRAIN_INTERVAL: Duration = Duration::from_millis(10);
+
+/// 노드의 종료 상태와 처리 중인 요청 수
+/// 종료가 시작되면 새 요청은 받지 않고, 처리 중인 요청이 끝날 때까지 기다릴 수 있다.
+#[derive(Default)]
+pub struct Lifecycle {
+    shutting_down: AtomicBool,
+    in_flight: AtomicUsize,
+}
+
+impl Lifecycle {
+    /// 요청 처리를 시작한다. 종료 중이면 `None`.
+    /// 돌려받은 guard가 drop될 때 요청이 끝난 것으로 본다.
+    pub fn enter(&self) -> Option<InFlight<'_>> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlight(self);
+        match self.is_shutting_down() {
+            true => None,
+            false => Some(guard),
+        }
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    pub fn begin_shutdown(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+    }
+
+    /// 처리 중인 요청이 모두 끝날 때까지 기다린다. `timeout`이 지나면 `false`.
+    pub fn drain(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while self.in_flight() > 0 {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(DRAIN_INTERVAL);
+        }
+        true
+    }
+}
+
+/// 처리 중인 요청 하나
+pub struct InFlight<'a>(&'a Lifecycle);
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
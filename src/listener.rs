@@ -1,6 +1,20 @@
+use std::net::TcpListener;
+#[cfg(unix)]
+use std::fs::DirBuilder;
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 use basic_http::server::HttpServer;
 use hub_channel::container::ChannelContainer;
+use log::warn;
+use crate::node::Node;
+use crate::websocket;
+#[cfg(unix)]
+use crate::ipc;
 
 pub struct RpcListener {
     socket: HttpServer,
@@ -10,10 +24,170 @@ impl RpcListener {
     pub fn new(ip: &str, port: u16, channel_container: Arc<Mutex<Arc<ChannelContainer>>>) -> Self {
         let mut server = HttpServer::new(ip, port, channel_container, None);
         server.append_handler("/", crate::handler::rpc_handler);
+        server.append_handler("/health", crate::health::health_handler);
+        server.append_handler("/ready", crate::health::ready_handler);
         Self { socket: server }
     }
 
     pub fn run(&self) {
         self.socket.bind();
     }
-}
\ No newline at end of file
+}
+
+/// WebSocket listener
+/// RpcListener와 같은 메서드를 처리하고, eth_subscribe 알림을 보낸다. 연결마다 스레드 하나를 쓴다.
+pub struct WsListener {
+    listener: TcpListener,
+}
+
+impl WsListener {
+    pub fn new(ip: &str, port: u16) -> std::io::Result<Self> {
+        Ok(Self { listener: TcpListener::bind((ip, port))? })
+    }
+
+    pub fn run(&self, node: &'static Node) {
+        for stream in self.listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let peer_addr = stream.peer_addr().map(|addr| addr.to_string()).unwrap_or_default();
+                    std::thread::spawn(move || websocket::accept(stream, &peer_addr, node));
+                }
+                Err(e) => warn!("[WEBSOCKET] accept failed: {}", e),
+            }
+        }
+    }
+}
+
+/// IPC 소켓 파일의 권한. 소켓을 만든 사용자만 접근할 수 있다.
+#[cfg(unix)]
+pub const IPC_SOCKET_MODE: u32 = 0o600;
+/// 소켓을 만드는 동안 잠깐 쓰는 디렉터리의 권한
+#[cfg(unix)]
+const IPC_PRIVATE_DIR_MODE: u32 = 0o700;
+/// 그 디렉터리 안에서 만드는 소켓의 이름
+#[cfg(unix)]
+const IPC_PRIVATE_SOCKET_NAME: &str = "s";
+
+/// Unix domain socket listener
+/// RpcListener와 같은 메서드를 처리한다. TCP 포트 없이 geth의 `.ipc`처럼 쓸 수 있다.
+#[cfg(unix)]
+pub struct IpcListener {
+    path: String,
+    listener: UnixListener,
+}
+
+#[cfg(unix)]
+impl IpcListener {
+    /// `path`에 소켓을 만든다. 이전 프로세스가 남긴 소켓 파일은 지우지만, 다른 노드가 쓰고 있으면 에러
+    /// 소켓이 아닌 파일이 있으면 지우지 않고 에러를 돌려준다.
+    pub fn new(path: &str) -> std::io::Result<Self> {
+        match std::fs::symlink_metadata(path) {
+            Ok(metadata) if !metadata.file_type().is_socket() => {
+                return Err(std::io::Error::new(std::io::ErrorKind::AlreadyExists, format!("{} exists and is not a socket", path)));
+            }
+            Ok(_) => {
+                if UnixStream::connect(path).is_ok() {
+                    return Err(std::io::Error::new(std::io::ErrorKind::AddrInUse, format!("{} is in use", path)));
+                }
+                warn!("[IPC] removing the stale socket {}", path);
+                std::fs::remove_file(path)?;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        let listener = bind_private(Path::new(path))?;
+        Ok(Self { path: path.to_string(), listener })
+    }
+
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    pub fn run(&self, node: &'static Node) {
+        for stream in self.listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let peer_addr = self.path.clone();
+                    std::thread::spawn(move || ipc::serve(stream, &peer_addr, node));
+                }
+                Err(e) => warn!("[IPC] accept failed: {}", e),
+            }
+        }
+    }
+}
+
+/// 권한을 바꾸기 전의 소켓에 다른 사용자가 접속하지 못하도록, 0700 디렉터리 안에서 소켓을 만들어
+/// 권한을 `IPC_SOCKET_MODE`로 바꾼 뒤 `path`에 링크한다. 그 사이에 `path`에 파일이 생겼으면 덮어쓰지 않고 에러
+#[cfg(unix)]
+fn bind_private(path: &Path) -> std::io::Result<UnixListener> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let private_dir = parent.join(format!(".{}.ipc", std::process::id()));
+    remove_stale_private_dir(&private_dir)?;
+    DirBuilder::new().mode(IPC_PRIVATE_DIR_MODE).create(&private_dir)?;
+    let private_path = private_dir.join(IPC_PRIVATE_SOCKET_NAME);
+    let listener = UnixListener::bind(&private_path).and_then(|listener| {
+        std::fs::set_permissions(&private_path, std::fs::Permissions::from_mode(IPC_SOCKET_MODE))?;
+        std::fs::hard_link(&private_path, path)?;
+        Ok(listener)
+    });
+    let _ = std::fs::remove_file(&private_path);
+    let _ = std::fs::remove_dir(&private_dir);
+    listener
+}
+
+/// `bind_private`가 정리하기 전에 멈춘 프로세스가 남긴 디렉터리를 지운다.
+/// 컨테이너에서는 pid가 매번 같아서 남겨 두면 다음 실행이 디렉터리를 만들지 못한다.
+/// 디렉터리가 아니면(심볼릭 링크 등) 따라가지 않고 에러를 돌려주고, 안의 소켓 말고 다른 파일이 있으면 `remove_dir`이 실패한다.
+#[cfg(unix)]
+fn remove_stale_private_dir(private_dir: &Path) -> std::io::Result<()> {
+    match std::fs::symlink_metadata(private_dir) {
+        Ok(metadata) if metadata.is_dir() => {
+            warn!("[IPC] removing the stale directory {}", private_dir.display());
+            let private_path = private_dir.join(IPC_PRIVATE_SOCKET_NAME);
+            if std::fs::symlink_metadata(&private_path).map(|metadata| metadata.file_type().is_socket()).unwrap_or(false) {
+                std::fs::remove_file(&private_path)?;
+            }
+            std::fs::remove_dir(private_dir)
+        }
+        Ok(_) => Err(std::io::Error::new(std::io::ErrorKind::AlreadyExists, format!("{} exists and is not a directory", private_dir.display()))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// 종료할 때 소켓 파일을 지운다.
+#[cfg(unix)]
+impl Drop for IpcListener {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use std::os::unix::net::UnixListener;
+    use super::{IpcListener, IPC_PRIVATE_DIR_MODE, IPC_PRIVATE_SOCKET_NAME, IPC_SOCKET_MODE};
+
+    /// 이전 실행이 소켓을 만들다가 멈춰 같은 pid의 디렉터리가 남아 있어도 다시 띄울 수 있다.
+    #[test]
+    fn stale_private_dir() {
+        let dir = std::env::temp_dir().join(format!("ipc-stale-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir(&dir).unwrap();
+        let private_dir = dir.join(format!(".{}.ipc", std::process::id()));
+        std::fs::DirBuilder::new().mode(IPC_PRIVATE_DIR_MODE).create(&private_dir).unwrap();
+        drop(UnixListener::bind(private_dir.join(IPC_PRIVATE_SOCKET_NAME)).unwrap());
+
+        let path = dir.join("node.ipc");
+        let listener = IpcListener::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, IPC_SOCKET_MODE);
+        assert!(!private_dir.exists());
+        drop(listener);
+        assert!(!path.exists());
+        std::fs::remove_dir(&dir).unwrap();
+    }
+}
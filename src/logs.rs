@@ -0,0 +1,192 @@
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+use std::sync::Mutex;
+use ethereum_types::{Address, Bloom, BloomInput, H256};
+use serde::Deserialize;
+use crate::block_tag::BlockTag;
+use crate::blockchain::Blockchain;
+use crate::bytes::Bytes;
+use crate::receipt::Log;
+use crate::response::RpcError;
+
+pub const DEFAULT_MAX_LOG_BLOCK_RANGE: u64 = 10_000;
+
+/// 값 하나 혹은 배열. 배열이면 그중 하나와 같으면 된다.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T: PartialEq> OneOrMany<T> {
+    pub fn values(&self) -> &[T] {
+        match self {
+            OneOrMany::One(value) => std::slice::from_ref(value),
+            OneOrMany::Many(values) => values.as_slice(),
+        }
+    }
+
+    /// 빈 배열은 모든 값과 맞는다.
+    fn matches(&self, value: &T) -> bool {
+        self.values().is_empty() || self.values().contains(value)
+    }
+}
+
+/// eth_getLogs, eth_newFilter의 필터 객체
+/// # [Fields]
+/// - `from_block: Option<BlockTag>`    - 없으면 latest
+/// - `to_block: Option<BlockTag>`      - 없으면 latest
+/// - `block_hash: Option<H256>`        - 블록 하나만 찾는다. `from_block`, `to_block`과 함께 쓸 수 없다.
+/// - `address: Option<OneOrMany<Address>>` - 로그를 남긴 계정
+/// - `topics: Option<Vec<Option<OneOrMany<H256>>>>` - 자리별 topic. null은 아무 값과 맞는다.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct LogFilter {
+    #[serde(rename="fromBlock", default)]
+    pub from_block: Option<BlockTag>,
+    #[serde(rename="toBlock", default)]
+    pub to_block: Option<BlockTag>,
+    #[serde(rename="blockHash", default)]
+    pub block_hash: Option<H256>,
+    #[serde(default)]
+    pub address: Option<OneOrMany<Address>>,
+    #[serde(default)]
+    pub topics: Option<Vec<Option<OneOrMany<H256>>>>,
+}
+
+impl LogFilter {
+    /// 찾을 블록 번호 범위. 최신 블록보다 뒤는 잘라낸다.
+    /// `fromBlock`이 최신 블록보다 뒤이면 빈 범위다. 두 블록 번호를 거꾸로 지정했을 때만 에러를 돌려준다.
+    /// `max_block_range`가 0이면 범위를 제한하지 않는다.
+    pub fn block_range(&self, chain: &Blockchain, max_block_range: u64) -> Result<RangeInclusive<u64>, RpcError> {
+        if let (Some(BlockTag::Number(from)), Some(BlockTag::Number(to))) = (self.from_block, self.to_block) {
+            if from > to {
+                return Err(RpcError::invalid_params("invalid block range"));
+            }
+        }
+        let (from, to) = self.bounds(chain, chain.number())?;
+        if from > to {
+            return Ok(from..=to);
+        }
+        if max_block_range > 0 && to - from + 1 > max_block_range {
+            return Err(RpcError::limit_exceeded(format!("query exceeds max block range {}", max_block_range).as_str()));
+        }
+        Ok(from..=to)
+    }
+
+    /// 필터가 가리키는 첫 블록과 마지막 블록. 최신 블록(`head`)보다 뒤는 잘라내며, 첫 블록이 더 클 수도 있다.
+    /// 호출하는 쪽이 같은 `head`로 다른 값도 계산할 수 있도록 최신 블록 번호를 받는다.
+    pub fn bounds(&self, chain: &Blockchain, head: u64) -> Result<(u64, u64), RpcError> {
+        if let Some(hash) = self.block_hash {
+            if self.from_block.is_some() || self.to_block.is_some() {
+                return Err(RpcError::invalid_params("cannot specify both blockHash and fromBlock/toBlock"));
+            }
+            let number = chain.block_by_hash(&hash)
+                .ok_or_else(|| RpcError::resource_not_found("unknown block"))?
+                .number();
+            return Ok((number, number));
+        }
+        let from = self.from_block.unwrap_or_default().resolve(head);
+        let to = self.to_block.unwrap_or_default().resolve(head).min(head);
+        Ok((from, to))
+    }
+
+    pub fn matches(&self, log: &Log) -> bool {
+        if let Some(address) = &self.address {
+            if !address.matches(&log.address) {
+                return false;
+            }
+        }
+        match &self.topics {
+            Some(topics) if topics.len() > log.topics.len() => false,
+            Some(topics) => topics.iter().zip(log.topics.iter())
+                .all(|(filter, topic)| filter.as_ref().map_or(true, |filter| filter.matches(topic))),
+            None => true,
+        }
+    }
+
+    /// 블록의 bloom에 필터가 찾는 값이 없으면 그 블록의 로그는 볼 필요가 없다.
+    pub fn matches_bloom(&self, bloom: &Bloom) -> bool {
+        let address_matches = self.address.as_ref().map_or(true, |address| {
+            address.values().is_empty() || address.values().iter()
+                .any(|address| bloom.contains_input(BloomInput::Raw(address.as_bytes())))
+        });
+        let topics_match = self.topics.iter().flatten().flatten().all(|topics| {
+            topics.values().is_empty() || topics.values().iter()
+                .any(|topic| bloom.contains_input(BloomInput::Raw(topic.as_bytes())))
+        });
+        address_matches && topics_match
+    }
+}
+
+/// 로그 목록의 2048비트 bloom. 주소와 topic을 모두 넣는다.
+pub fn logs_bloom<'a>(logs: impl IntoIterator<Item = &'a Log>) -> Bloom {
+    let mut bloom = Bloom::zero();
+    for log in logs {
+        bloom.accrue(BloomInput::Raw(log.address.as_bytes()));
+        for topic in log.topics.iter() {
+            bloom.accrue(BloomInput::Raw(topic.as_bytes()));
+        }
+    }
+    bloom
+}
+
+struct BlockLogs {
+    bloom: Bloom,
+    logs: Vec<Log>,
+}
+
+/// 블록 번호별로 로그를 보관한다.
+#[derive(Default)]
+pub struct LogStore {
+    blocks: Mutex<BTreeMap<u64, BlockLogs>>,
+}
+
+impl LogStore {
+    pub fn insert(&self, number: u64, bloom: Bloom, logs: Vec<Log>) {
+        self.blocks.lock().unwrap_or_else(|e| e.into_inner()).insert(number, BlockLogs { bloom, logs });
+    }
+
+    /// `range` 안의 블록에서 필터와 맞는 로그를 블록, 로그 순서대로 돌려준다.
+    pub fn logs(&self, range: RangeInclusive<u64>, filter: &LogFilter) -> Vec<Log> {
+        if range.is_empty() {
+            return vec![];
+        }
+        self.blocks.lock().unwrap_or_else(|e| e.into_inner())
+            .range(range)
+            .filter(|(_, block)| filter.matches_bloom(&block.bloom))
+            .flat_map(|(_, block)| block.logs.iter().filter(|log| filter.matches(log)).cloned())
+            .collect()
+    }
+}
+
+/// 트랜잭션이 남길 로그. 블록 정보는 블록을 만들 때 채운다.
+#[derive(Deserialize, Clone, Debug)]
+pub struct ScriptedLog {
+    #[serde(default)]
+    pub topics: Vec<H256>,
+    #[serde(default)]
+    pub data: Bytes,
+}
+
+/// EVM을 실행하지 않으므로, 계정별로 정해 둔 로그를 그 계정으로 가는 트랜잭션이 남긴 것으로 본다.
+/// 컨트랙트를 만드는 트랜잭션은 만들어진 주소의 로그를 남긴다.
+#[derive(Default)]
+pub struct LogScripts {
+    scripts: Mutex<HashMap<Address, Vec<ScriptedLog>>>,
+}
+
+impl LogScripts {
+    /// `logs`가 비어 있으면 지운다.
+    pub fn set(&self, address: Address, logs: Vec<ScriptedLog>) {
+        let mut scripts = self.scripts.lock().unwrap_or_else(|e| e.into_inner());
+        match logs.is_empty() {
+            true => scripts.remove(&address),
+            false => scripts.insert(address, logs),
+        };
+    }
+
+    pub fn get(&self, address: &Address) -> Vec<ScriptedLog> {
+        self.scripts.lock().unwrap_or_else(|e| e.into_inner()).get(address).cloned().unwrap_or_default()
+    }
+}
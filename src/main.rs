@@ -1,27 +1,96 @@
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use hub_channel::hub::ChannelHub;
-use log::{Level, LevelFilter, Metadata, Record, SetLoggerError};
-
-mod consts;
-mod handler;
-mod listener;
-mod methods;
-mod request;
-mod response;
-mod account;
-mod logging;
-mod signer;
-mod block;
-mod transaction;
-mod raw_transaction;
-pub mod utils;
-
+use log::LevelFilter;
+use metamask_sign_checker::config::{Config, ConfigError, USAGE};
+use metamask_sign_checker::listener::{RpcListener, WsListener};
+#[cfg(unix)]
+use metamask_sign_checker::listener::IpcListener;
+use metamask_sign_checker::logging;
+use metamask_sign_checker::methods::registry::MethodRegistry;
+use metamask_sign_checker::miner::{self, MiningMode};
+use metamask_sign_checker::node::Node;
 
 fn main() {
-    crate::logging::init();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let config = match Config::from_args(&args) {
+        Ok(config) => config,
+        Err(ConfigError::Help) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = logging::init(config.log_level_filter().unwrap_or(LevelFilter::Info)) {
+        eprintln!("could not initialize the logger: {}", e);
+    }
     log::info!("Metamask sign checker is running now");
-    let mut hub = ChannelHub::new();
-    let rpc_container = Arc::new(Mutex::new(hub.new_container("rpc")));
-    let rpc = crate::listener::RpcListener::new("0.0.0.0", 8545, rpc_container);
-    rpc.run();
+    log::info!("chain_id={} network_id={} client_version='{}'", config.chain_id, config.network_id(), config.client_version);
+    // 사용자 정의 메서드는 Node를 만들기 전에 registry.register(...)로 등록한다.
+    let registry = MethodRegistry::with_default_methods();
+    log::info!("[JSON_RPC] registered methods: {}", registry.methods().join(", "));
+    log::info!("[JSON_RPC] unsupported methods: {}", registry.unsupported().join(", "));
+    let bind_address = config.bind_address.clone();
+    let port = config.port;
+    let ws_port = config.ws_port;
+    #[cfg(unix)]
+    let ipc_path = config.ipc_path.clone();
+    let shutdown_timeout = Duration::from_secs(config.shutdown_timeout);
+    let mining = config.mining;
+    log::info!("[MINER] mining={} block_time={}", mining, config.block_time);
+    Node::new(config, registry).install();
+
+    // HttpServer::bind는 돌아오지 않으므로 listener는 별도 스레드에서 돌리고,
+    // 메인 스레드는 SIGINT/SIGTERM을 기다렸다가 처리 중인 요청을 마무리하고 종료한다.
+    let (signal_tx, signal_rx) = std::sync::mpsc::channel();
+    if let Err(e) = ctrlc::set_handler(move || { let _ = signal_tx.send(()); }) {
+        log::warn!("could not install the signal handler: {}", e);
+    }
+    if ws_port != 0 {
+        match WsListener::new(bind_address.as_str(), ws_port) {
+            Ok(ws) => {
+                log::info!("[WEBSOCKET] listening on {}:{}", bind_address, ws_port);
+                std::thread::spawn(move || ws.run(Node::global()));
+            }
+            Err(e) => log::warn!("[WEBSOCKET] could not bind {}:{}: {}", bind_address, ws_port, e),
+        }
+    }
+    // 만든 소켓만 종료할 때 지운다. 만들지 못했으면 그 경로에 있는 파일은 다른 것이다.
+    #[cfg(unix)]
+    let ipc_socket = ipc_path.and_then(|ipc_path| match IpcListener::new(ipc_path.as_str()) {
+        Ok(ipc) => {
+            log::info!("[IPC] listening on {}", ipc.path());
+            std::thread::spawn(move || ipc.run(Node::global()));
+            Some(ipc_path)
+        }
+        Err(e) => {
+            log::warn!("[IPC] could not bind {}: {}", ipc_path, e);
+            None
+        }
+    });
+    #[cfg(not(unix))]
+    let ipc_socket: Option<String> = None;
+    std::thread::spawn(move || {
+        let mut hub = ChannelHub::new();
+        let rpc_container = Arc::new(Mutex::new(hub.new_container("rpc")));
+        let rpc = RpcListener::new(bind_address.as_str(), port, rpc_container);
+        rpc.run();
+    });
+    if mining == MiningMode::Interval {
+        std::thread::spawn(|| miner::run_interval(Node::global()));
+    }
+
+    let _ = signal_rx.recv();
+    log::info!("shutdown signal received");
+    let drained = Node::global().shutdown(shutdown_timeout);
+    // listener 스레드는 끝나지 않으므로 IPC 소켓 파일은 여기서 지운다.
+    if let Some(ipc_socket) = ipc_socket {
+        let _ = std::fs::remove_file(ipc_socket);
+    }
+    log::info!("Metamask sign checker stopped");
+    log::logger().flush();
+    std::process::exit(if drained { 0 } else { 1 });
 }
@@ -0,0 +1,17 @@
+use crate::bytes::Bytes;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
+use crate::typed_transaction::TypedTransaction;
+
+/// 서명된 트랜잭션을 노드에 보내지 않고 해석한 결과만 돌려주는 디버그용 RPC
+pub struct DebugRawTransaction;
+
+impl RpcCall for DebugRawTransaction {
+    type Params = (Bytes,);
+    type Result = TypedTransaction;
+
+    fn receive(&self, params: (Bytes,), _ctx: &RpcContext) -> Result<TypedTransaction, RpcError> {
+        TypedTransaction::decode(params.0.as_slice())
+            .map_err(|e| RpcError::invalid_params(&format!("{:?}", e)))
+    }
+}
@@ -0,0 +1,18 @@
+use std::collections::BTreeMap;
+use crate::methods::{RpcCall, RpcContext};
+use crate::request::EmptyParams;
+use crate::response::RpcError;
+
+/// 메서드별로 복구한 panic 횟수를 제공하는 디버그용 RPC
+/// # Example
+/// * {"eth_sendRawTransaction":2}
+pub struct DebugRecoveredPanics;
+
+impl RpcCall for DebugRecoveredPanics {
+    type Params = EmptyParams;
+    type Result = BTreeMap<String, u64>;
+
+    fn receive(&self, _params: EmptyParams, ctx: &RpcContext) -> Result<BTreeMap<String, u64>, RpcError> {
+        Ok(ctx.node.registry.recovered_panics())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod debug_raw_transaction;
+pub mod debug_recovered_panics;
@@ -0,0 +1,16 @@
+use ethereum_types::Address;
+use crate::methods::{RpcCall, RpcContext};
+use crate::request::EmptyParams;
+use crate::response::RpcError;
+
+/// 노드가 관리하는 계정이 없으므로 항상 빈 배열을 돌려준다.
+pub struct EthAccounts;
+
+impl RpcCall for EthAccounts {
+    type Params = EmptyParams;
+    type Result = Vec<Address>;
+
+    fn receive(&self, _params: EmptyParams, _ctx: &RpcContext) -> Result<Vec<Address>, RpcError> {
+        Ok(vec![])
+    }
+}
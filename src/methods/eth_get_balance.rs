@@ -1,49 +1,25 @@
-use std::str::FromStr;
-use std::sync::Mutex;
-use hub_channel::channel::Channel;
-use crate::consts::RPC_VERSION;
-use crate::methods::RpcCall;
-use crate::request::{CommonRpcStringsRequest, RpcStringsRequest, RpcStringsRequestWithUuid};
-use crate::response::{RpcStringResponse, RpcStringResponseWithUuid};
+use ethereum_types::{Address, U256};
+use serde::Deserialize;
+use crate::block_tag::BlockTag;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
 
-pub struct EthGetBalance(Box<dyn CommonRpcStringsRequest>);
-
-impl From<Box<dyn CommonRpcStringsRequest>> for EthGetBalance {
-    fn from(request: Box<dyn CommonRpcStringsRequest>) -> Self {
-        Self { 0: request }
-    }
+/// `[address, block]`
+#[derive(Deserialize)]
+pub struct EthGetBalanceParams {
+    pub address: Address,
+    #[serde(default)]
+    pub block: BlockTag,
 }
 
+pub struct EthGetBalance;
+
 impl RpcCall for EthGetBalance {
-    fn call(&self) -> String {
-        return match self.0.is_uuid() {
-            true => {
-                let request = RpcStringsRequestWithUuid::new(self.0.str_id().as_str(), RPC_VERSION, self.0.method(), self.0.params());
-                serde_json::to_string(&request).unwrap()
-            }
-            false => {
-                let id = u64::from_str(self.0.str_id().as_str()).unwrap();
-                let request = RpcStringsRequest::new(&id, RPC_VERSION, self.0.method(), self.0.params());
-                serde_json::to_string(&request).unwrap()
-            }
-        }
-        // serde_json::to_string::<RpcStringsRequest>(&self.0).unwrap()
-    }
+    type Params = EthGetBalanceParams;
+    type Result = U256;
 
-    fn receive(&self, ch: &Mutex<Channel>) -> String {
-        const ZERO_BALANCE: &str = "0xFFFFFFFFFFFFFFFF";
-        return match self.0.is_uuid() {
-            true => {
-                let res = RpcStringResponseWithUuid::new(self.0.str_id().as_str(), &ZERO_BALANCE.to_string());
-                serde_json::to_string(&res).unwrap()
-            }
-            false => {
-                let id = u64::from_str(self.0.str_id().as_str()).unwrap();
-                let res = RpcStringResponse::new(&id, &ZERO_BALANCE.to_string());
-                serde_json::to_string(&res).unwrap()
-            }
-        }
-        // let res = RpcStringResponse::new(self.0.id, &"0x0".to_string());
-        // serde_json::to_string(&res).unwrap()
+    fn receive(&self, params: EthGetBalanceParams, ctx: &RpcContext) -> Result<U256, RpcError> {
+        ctx.node.state.balance(&params.address, params.block)
+            .ok_or_else(|| RpcError::resource_not_found("header not found"))
     }
-}
\ No newline at end of file
+}
@@ -1,50 +1,27 @@
-use std::str::FromStr;
-use std::sync::Mutex;
-use hub_channel::channel::Channel;
-use crate::consts::RPC_VERSION;
-use crate::methods::RpcCall;
-use crate::request::{CommonRpcStringsRequest, RpcStringsRequest, RpcStringsRequestWithUuid};
-use crate::response::{RpcStringResponse, RpcStringResponseWithUuid};
+use ethereum_types::H256;
+use serde::Deserialize;
+use crate::block::Block;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
 
-pub struct EthGetBlockByHash(Box<dyn CommonRpcStringsRequest>);
-
-impl From<Box<dyn CommonRpcStringsRequest>> for EthGetBlockByHash {
-    fn from(request: Box<dyn CommonRpcStringsRequest>) -> Self {
-        Self { 0: request }
-    }
+/// `[block_hash, full_transactions]`
+#[derive(Deserialize)]
+pub struct EthGetBlockByHashParams {
+    pub hash: H256,
+    #[serde(default)]
+    pub full_transactions: bool,
 }
 
-impl RpcCall for EthGetBlockByHash {
-    fn call(&self) -> String {
-        match self.0.is_uuid() {
-            true => {
-                let request = RpcStringsRequestWithUuid::new(self.0.str_id().as_str(), RPC_VERSION, self.0.method(), self.0.params());
-                serde_json::to_string(&request).unwrap()
-            }
-            false => {
-                let id = u64::from_str(&self.0.str_id().as_str()).unwrap();
-                let request = RpcStringsRequest::new(&id, RPC_VERSION, self.0.method(), self.0.params());
-                serde_json::to_string(&request).unwrap()
-            }
-        }
-        // serde_json::to_string::<RpcStringsRequest>(&self.0).unwrap()
-    }
+/// 모르는 해시이면 null
+pub struct EthGetBlockByHash;
 
-    fn receive(&self, ch: &Mutex<Channel>) -> String {
-        let result = "0x0";
+impl RpcCall for EthGetBlockByHash {
+    type Params = EthGetBlockByHashParams;
+    type Result = Option<Block>;
 
-        return match self.0.is_uuid() {
-            true => {
-                let res = RpcStringResponseWithUuid::new(self.0.str_id().as_str(), &result);
-                serde_json::to_string(&res).unwrap()
-            }
-            false => {
-                let id = u64::from_str(self.0.str_id().as_str()).unwrap();
-                let res = RpcStringResponse::new(&id, &result);
-                serde_json::to_string(&res).unwrap()
-            }
-        }
-        // let res = RpcStringResponse::new(self.0.id, &result);
-        // serde_json::to_string::<RpcStringResponse>(&res).unwrap()
+    fn receive(&self, params: EthGetBlockByHashParams, ctx: &RpcContext) -> Result<Option<Block>, RpcError> {
+        Ok(ctx.node.chain.block_by_hash(&params.hash).map(|block| {
+            Block::new(&block, ctx.node.transactions.block_transactions(&block, params.full_transactions, &ctx.node.receipts))
+        }))
     }
-}
\ No newline at end of file
+}
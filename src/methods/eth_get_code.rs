@@ -0,0 +1,27 @@
+use ethereum_types::Address;
+use serde::Deserialize;
+use crate::block_tag::BlockTag;
+use crate::bytes::Bytes;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
+
+/// `[address, block]`
+#[derive(Deserialize)]
+pub struct EthGetCodeParams {
+    pub address: Address,
+    #[serde(default)]
+    pub block: BlockTag,
+}
+
+/// EIP-7702로 위임한 계정은 `0xef0100 || address`를 돌려준다.
+pub struct EthGetCode;
+
+impl RpcCall for EthGetCode {
+    type Params = EthGetCodeParams;
+    type Result = Bytes;
+
+    fn receive(&self, params: EthGetCodeParams, ctx: &RpcContext) -> Result<Bytes, RpcError> {
+        ctx.node.state.code(&params.address, params.block)
+            .ok_or_else(|| RpcError::resource_not_found("header not found"))
+    }
+}
@@ -0,0 +1,17 @@
+use ethereum_types::U256;
+use crate::filters::FilterChanges;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
+
+/// `[filter_id]`
+/// 지난 조회 이후의 로그 혹은 블록, 트랜잭션 해시
+pub struct EthGetFilterChanges;
+
+impl RpcCall for EthGetFilterChanges {
+    type Params = (U256,);
+    type Result = FilterChanges;
+
+    fn receive(&self, params: (U256,), ctx: &RpcContext) -> Result<FilterChanges, RpcError> {
+        ctx.node.filters.changes(&params.0, &ctx.node.chain, &ctx.node.logs)
+    }
+}
@@ -0,0 +1,17 @@
+use ethereum_types::U256;
+use crate::methods::{RpcCall, RpcContext};
+use crate::receipt::Log;
+use crate::response::RpcError;
+
+/// `[filter_id]`
+/// eth_newFilter로 만든 필터와 맞는 로그 전체. eth_getLogs와 같은 블록 범위 제한을 따른다.
+pub struct EthGetFilterLogs;
+
+impl RpcCall for EthGetFilterLogs {
+    type Params = (U256,);
+    type Result = Vec<Log>;
+
+    fn receive(&self, params: (U256,), ctx: &RpcContext) -> Result<Vec<Log>, RpcError> {
+        ctx.node.filters.logs(&params.0, &ctx.node.chain, &ctx.node.logs, ctx.node.config.max_log_block_range)
+    }
+}
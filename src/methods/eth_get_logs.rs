@@ -0,0 +1,19 @@
+use crate::logs::LogFilter;
+use crate::methods::{RpcCall, RpcContext};
+use crate::receipt::Log;
+use crate::response::RpcError;
+
+/// `[filter]`
+/// 블록 범위가 `max_log_block_range`보다 넓으면 limit exceeded 에러
+pub struct EthGetLogs;
+
+impl RpcCall for EthGetLogs {
+    type Params = (LogFilter,);
+    type Result = Vec<Log>;
+
+    fn receive(&self, params: (LogFilter,), ctx: &RpcContext) -> Result<Vec<Log>, RpcError> {
+        let filter = params.0;
+        let range = filter.block_range(&ctx.node.chain, ctx.node.config.max_log_block_range)?;
+        Ok(ctx.node.logs.logs(range, &filter))
+    }
+}
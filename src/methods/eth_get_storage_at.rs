@@ -0,0 +1,28 @@
+use ethereum_types::{Address, H256, U256};
+use serde::Deserialize;
+use crate::block_tag::BlockTag;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
+use crate::typed_transaction::h256_from_u256;
+
+/// `[address, position, block]`
+#[derive(Deserialize)]
+pub struct EthGetStorageAtParams {
+    pub address: Address,
+    pub position: U256,
+    #[serde(default)]
+    pub block: BlockTag,
+}
+
+/// 쓴 적 없는 슬롯은 0
+pub struct EthGetStorageAt;
+
+impl RpcCall for EthGetStorageAt {
+    type Params = EthGetStorageAtParams;
+    type Result = H256;
+
+    fn receive(&self, params: EthGetStorageAtParams, ctx: &RpcContext) -> Result<H256, RpcError> {
+        ctx.node.state.storage(&params.address, &h256_from_u256(&params.position), params.block)
+            .ok_or_else(|| RpcError::resource_not_found("header not found"))
+    }
+}
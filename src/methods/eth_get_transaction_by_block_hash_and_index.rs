@@ -0,0 +1,19 @@
+use ethereum_types::{H256, U64};
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
+use crate::transaction::Transaction;
+
+/// `[block_hash, index]`
+/// 모르는 블록이거나 index가 범위를 넘으면 null
+pub struct EthGetTransactionByBlockHashAndIndex;
+
+impl RpcCall for EthGetTransactionByBlockHashAndIndex {
+    type Params = (H256, U64);
+    type Result = Option<Transaction>;
+
+    fn receive(&self, params: (H256, U64), ctx: &RpcContext) -> Result<Option<Transaction>, RpcError> {
+        Ok(ctx.node.chain.block_by_hash(&params.0)
+            .and_then(|block| block.transactions.get(params.1.as_usize()).cloned())
+            .and_then(|hash| ctx.node.transactions.rpc_transaction(&hash, &ctx.node.receipts)))
+    }
+}
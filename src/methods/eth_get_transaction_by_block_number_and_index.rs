@@ -0,0 +1,20 @@
+use ethereum_types::U64;
+use crate::block_tag::BlockTag;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
+use crate::transaction::Transaction;
+
+/// `[block, index]`
+/// 아직 만들지 않은 블록이거나 index가 범위를 넘으면 null
+pub struct EthGetTransactionByBlockNumberAndIndex;
+
+impl RpcCall for EthGetTransactionByBlockNumberAndIndex {
+    type Params = (BlockTag, U64);
+    type Result = Option<Transaction>;
+
+    fn receive(&self, params: (BlockTag, U64), ctx: &RpcContext) -> Result<Option<Transaction>, RpcError> {
+        Ok(ctx.node.chain.block(params.0)
+            .and_then(|block| block.transactions.get(params.1.as_usize()).cloned())
+            .and_then(|hash| ctx.node.transactions.rpc_transaction(&hash, &ctx.node.receipts)))
+    }
+}
@@ -0,0 +1,16 @@
+use ethereum_types::H256;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
+use crate::transaction::Transaction;
+
+/// 받은 적 없는 해시이면 null
+pub struct EthGetTransactionByHash;
+
+impl RpcCall for EthGetTransactionByHash {
+    type Params = (H256,);
+    type Result = Option<Transaction>;
+
+    fn receive(&self, params: (H256,), ctx: &RpcContext) -> Result<Option<Transaction>, RpcError> {
+        Ok(ctx.node.transactions.rpc_transaction(&params.0, &ctx.node.receipts))
+    }
+}
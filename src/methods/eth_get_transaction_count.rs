@@ -1,55 +1,26 @@
-use std::fmt::format;
-use std::str::FromStr;
-use std::sync::Mutex;
-use ethereum_types::Address;
-use hub_channel::channel::Channel;
-use crate::consts::RPC_VERSION;
-use crate::methods::RpcCall;
-use crate::request::{CommonRpcStringsRequest, RpcStringsRequest, RpcStringsRequestWithUuid};
-use crate::response::{RpcStringResponse, RpcStringResponseWithUuid};
+use ethereum_types::{Address, U64};
+use serde::Deserialize;
+use crate::block_tag::BlockTag;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
 
-pub struct EthGetTransactionCount(Box<dyn CommonRpcStringsRequest>);
-
-impl From<Box<dyn CommonRpcStringsRequest>> for EthGetTransactionCount {
-    fn from(request: Box<dyn CommonRpcStringsRequest>) -> Self {
-        Self { 0: request }
-    }
+/// `[address, block]`
+#[derive(Deserialize)]
+pub struct EthGetTransactionCountParams {
+    pub address: Address,
+    #[serde(default)]
+    pub block: BlockTag,
 }
 
-impl RpcCall for EthGetTransactionCount {
-    fn call(&self) -> String {
-        return match self.0.is_uuid() {
-            true => {
-                let request = RpcStringsRequestWithUuid::new(self.0.str_id().as_str(), RPC_VERSION, self.0.method(), self.0.params());
-                serde_json::to_string(&request).unwrap()
-            }
-            false => {
-                let id = u64::from_str(self.0.str_id().as_str()).unwrap();
-                let request = RpcStringsRequest::new(&id, RPC_VERSION, self.0.method(), self.0.params());
-                serde_json::to_string(&request).unwrap()
-            }
-        }
-        // serde_json::to_string::<RpcStringsRequest>(&self.0).unwrap()
-    }
+/// 계정의 nonce. 지갑은 이 값으로 다음 트랜잭션의 nonce를 정한다.
+pub struct EthGetTransactionCount;
 
-    fn receive(&self, ch: &Mutex<Channel>) -> String {
-        let str_address = self.0.params().get(0).unwrap().as_str().unwrap().split_at(2).1;
-        let hex_address = hex::decode(str_address).unwrap();
-        let address = Address::from_slice(hex_address.as_slice());
-        let tx_count = format!("{:x}", 1u64);
+impl RpcCall for EthGetTransactionCount {
+    type Params = EthGetTransactionCountParams;
+    type Result = U64;
 
-        return match self.0.is_uuid() {
-            true => {
-                let res = RpcStringResponseWithUuid::new(self.0.str_id().as_str(), tx_count.as_str());
-                serde_json::to_string::<RpcStringResponseWithUuid>(&res).unwrap()
-            }
-            false => {
-                let id = u64::from_str(self.0.str_id().as_str()).unwrap();
-                let res = RpcStringResponse::new(&id, tx_count.as_str());
-                serde_json::to_string::<RpcStringResponse>(&res).unwrap()
-            }
-        }
-        // let res = RpcStringResponse::new(self.0.id, tx_count.as_str());
-        // serde_json::to_string::<RpcStringResponse>(&res).unwrap()
+    fn receive(&self, params: EthGetTransactionCountParams, ctx: &RpcContext) -> Result<U64, RpcError> {
+        ctx.node.state.nonce(&params.address, params.block)
+            .ok_or_else(|| RpcError::resource_not_found("header not found"))
     }
-}
\ No newline at end of file
+}
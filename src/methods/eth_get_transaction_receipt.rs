@@ -0,0 +1,16 @@
+use ethereum_types::H256;
+use crate::methods::{RpcCall, RpcContext};
+use crate::receipt::Receipt;
+use crate::response::RpcError;
+
+/// 아직 블록에 들어가지 않았거나 받은 적 없는 해시이면 null
+pub struct EthGetTransactionReceipt;
+
+impl RpcCall for EthGetTransactionReceipt {
+    type Params = (H256,);
+    type Result = Option<Receipt>;
+
+    fn receive(&self, params: (H256,), ctx: &RpcContext) -> Result<Option<Receipt>, RpcError> {
+        Ok(ctx.node.receipts.get(&params.0))
+    }
+}
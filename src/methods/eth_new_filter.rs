@@ -0,0 +1,18 @@
+use ethereum_types::U256;
+use crate::filters::FilterKind;
+use crate::logs::LogFilter;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
+
+/// `[filter]`
+/// 다음 블록부터 조건에 맞는 로그를 eth_getFilterChanges로 돌려주는 필터를 만든다.
+pub struct EthNewFilter;
+
+impl RpcCall for EthNewFilter {
+    type Params = (LogFilter,);
+    type Result = U256;
+
+    fn receive(&self, params: (LogFilter,), ctx: &RpcContext) -> Result<U256, RpcError> {
+        ctx.node.filters.install(FilterKind::Logs(params.0), &ctx.node.chain)
+    }
+}
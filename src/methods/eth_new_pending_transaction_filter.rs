@@ -0,0 +1,17 @@
+use ethereum_types::U256;
+use crate::filters::FilterKind;
+use crate::methods::{RpcCall, RpcContext};
+use crate::request::EmptyParams;
+use crate::response::RpcError;
+
+/// 새로 받은 트랜잭션 해시를 eth_getFilterChanges로 돌려주는 필터를 만든다.
+pub struct EthNewPendingTransactionFilter;
+
+impl RpcCall for EthNewPendingTransactionFilter {
+    type Params = EmptyParams;
+    type Result = U256;
+
+    fn receive(&self, _params: EmptyParams, ctx: &RpcContext) -> Result<U256, RpcError> {
+        ctx.node.filters.install(FilterKind::PendingTransactions, &ctx.node.chain)
+    }
+}
@@ -1,63 +1,119 @@
-use std::str::FromStr;
-use std::sync::Mutex;
 use crypto::hash::keccak256;
-use hub_channel::channel::Channel;
-use crate::consts::RPC_VERSION;
-use crate::methods::RpcCall;
-use crate::raw_transaction::{MetamaskRawTransaction, RawTransaction};
-use crate::request::{CommonRpcStringsRequest, RpcStringsRequest, RpcStringsRequestWithUuid};
-use crate::response::{RpcStringResponse, RpcStringResponseWithUuid};
+use ethereum_types::{H256, U256};
+use log::{info, warn};
+use crate::blob_transaction::{blob_gas_price, calc_excess_blob_gas};
+use crate::block_tag::BlockTag;
+use crate::bytes::Bytes;
+use crate::methods::{RpcCall, RpcContext};
+use crate::miner::{self, MiningMode};
+use crate::node::ReceivedTransaction;
+use crate::transaction_pool::PooledTransaction;
+use crate::response::RpcError;
+use crate::set_code_transaction::{delegation_code, is_delegation, SetCodeTransaction};
+use crate::signature_report::SignatureReport;
+use crate::typed_transaction::TypedTransaction;
 
-pub struct EthSendRawTransaction(Box<dyn CommonRpcStringsRequest>);
-
-impl From<Box<dyn CommonRpcStringsRequest>> for EthSendRawTransaction {
-    fn from(request: Box<dyn CommonRpcStringsRequest>) -> Self {
-        Self { 0: request }
-    }
-}
+pub struct EthSendRawTransaction;
 
 impl RpcCall for EthSendRawTransaction {
-    fn call(&self) -> String {
-        match self.0.is_uuid() {
-            true => {
-                let request = RpcStringsRequestWithUuid::new(self.0.str_id().as_str(), RPC_VERSION, self.0.method(), self.0.params());
-                serde_json::to_string(&request).unwrap()
+    type Params = (Bytes,);
+    type Result = H256;
+
+    fn receive(&self, params: (Bytes,), ctx: &RpcContext) -> Result<H256, RpcError> {
+        let raw_tx = params.0;
+        let decoded = TypedTransaction::decode(raw_tx.as_slice());
+        let tx_hash = match &decoded {
+            Ok(tx) => tx.hash(raw_tx.as_slice()),
+            Err(_) => keccak256(raw_tx.as_slice()),
+        };
+        ctx.node.history.record_transaction(ReceivedTransaction { hash: tx_hash, raw: raw_tx.clone() });
+
+        let tx = decoded
+            .map_err(|e| RpcError::invalid_params(&format!("could not decode the transaction: {:?}", e)))?;
+        let report = SignatureReport::new(tx_hash, &tx, ctx.node.config.chain_id);
+        match report.valid {
+            true => info!("[SIGNATURE] tx={:?} type={} signing_hash={:?} sender={:?} chain_id={:?} passed",
+                tx_hash, tx.tx_type(), report.signing_hash, report.sender, report.chain_id),
+            false => warn!("[SIGNATURE] tx={:?} type={} signing_hash={:?} sender={:?} chain_id={:?} failed: {}",
+                tx_hash, tx.tx_type(), report.signing_hash, report.sender, report.chain_id, report.reasons.join("; ")),
+        }
+        let sender = report.sender;
+        let reasons = report.reasons.join("; ");
+        ctx.node.signature_reports.insert(report);
+        // 보낸 사람을 알 수 없는 트랜잭션은 상태에 반영하지 않는다.
+        let from = sender.ok_or_else(|| RpcError::invalid_input(&format!("invalid sender: {}", reasons)))?;
+
+        if let Err(reason) = tx.validate() {
+            warn!("[JSON_RPC] tx={:?} type={} rejected: {}", tx_hash, tx.tx_type(), reason);
+            return Err(RpcError::invalid_input(&reason));
+        }
+        if let Some(chain_id) = tx.chain_id() {
+            if chain_id != ctx.node.config.chain_id {
+                warn!("[JSON_RPC] tx={:?} chain id mismatch: have {} want {}", tx_hash, chain_id, ctx.node.config.chain_id);
+                return Err(RpcError::invalid_input(&format!("invalid chain id for signer: have {} want {}", chain_id, ctx.node.config.chain_id)));
             }
-            false => {
-                let id = u64::from_str(&self.0.str_id().as_str()).unwrap();
-                let request = RpcStringsRequest::new(&id, RPC_VERSION, self.0.method(), self.0.params());
-                serde_json::to_string(&request).unwrap()
+        }
+        let base_fee_per_gas = U256::from(ctx.node.config.gas_price);
+        let submitted = ctx.node.chain.submit(tx_hash, |head| {
+            let blob_base_fee = blob_gas_price(calc_excess_blob_gas(&head.header));
+            ctx.node.state.apply_transaction(&from, &tx, Some(base_fee_per_gas), blob_base_fee)?;
+            if let TypedTransaction::SetCode(stx) = &tx {
+                apply_authorizations(stx, tx_hash, ctx);
             }
+            ctx.node.transactions.insert(PooledTransaction { hash: tx_hash, from, tx: tx.clone() });
+            Ok(())
+        });
+        if let Err(reason) = submitted {
+            warn!("[JSON_RPC] tx={:?} type={} rejected: {}", tx_hash, tx.tx_type(), reason);
+            return Err(RpcError::invalid_input(&reason));
+        }
+        ctx.node.filters.notify_pending_transaction(tx_hash);
+        ctx.node.subscriptions.notify_pending_transaction(tx_hash);
+        match &tx {
+            TypedTransaction::DynamicFee(dtx) => info!("[JSON_RPC] tx={:?} type={} from={:?} chain_id={} max_fee_per_gas={} max_priority_fee_per_gas={}",
+                tx_hash, tx.tx_type(), from, dtx.chain_id, dtx.max_fee_per_gas, dtx.max_priority_fee_per_gas),
+            TypedTransaction::Blob(btx) => info!("[JSON_RPC] tx={:?} type={} from={:?} chain_id={} max_fee_per_blob_gas={} blobs={}",
+                tx_hash, tx.tx_type(), from, btx.chain_id, btx.max_fee_per_blob_gas, btx.blob_versioned_hashes.len()),
+            _ => info!("[JSON_RPC] tx={:?} type={} from={:?} chain_id={:?}", tx_hash, tx.tx_type(), from, tx.chain_id()),
+        }
+        if let Some(contract_address) = tx.contract_address(&from) {
+            info!("[JSON_RPC] tx={:?} creates a contract at {:?}", tx_hash, contract_address);
+        }
+        if ctx.node.config.mining == MiningMode::Auto {
+            miner::mine(ctx.node, None);
         }
-        // serde_json::to_string::<RpcStringsRequest>(&self.0).unwrap()
-    }
-
-    fn receive(&self, ch: &Mutex<Channel>) -> String {
-        let str_raw_tx = self.0.params().get(0).unwrap().as_str().unwrap().split_at(2).1;
-        let mut result = "0x".to_string();
-
-        let raw_tx = hex::decode(str_raw_tx).unwrap();
-        let tx_hash = keccak256(raw_tx.as_slice());
-        let str_tx_hash = hex::encode(tx_hash);
-        result.push_str(str_tx_hash.as_str());
 
-        let mrtx = rlp::decode::<MetamaskRawTransaction>(&raw_tx).unwrap();
-        let rtx = RawTransaction::from(mrtx);
-        // crate::signer::Eip155Signer::sign(rtx.nonce, )
-        // crypto::ecdsa::public_key::PublicKey::from_signature(rtx.v.clone() as i32, )
+        Ok(tx_hash)
+    }
+}
 
-        match self.0.is_uuid() {
-            true => {
-                let res = RpcStringResponseWithUuid::new(self.0.str_id().as_str(), &result);
-                serde_json::to_string::<RpcStringResponseWithUuid>(&res).unwrap()
-            }
-            false => {
-                let id = u64::from_str(self.0.str_id().as_str()).unwrap();
-                let res = RpcStringResponse::new(&id, &result);
-                serde_json::to_string::<RpcStringResponse>(&res).unwrap()
+/// authorization tuple마다 authority를 복구해서 로그로 남기고,
+/// 유효한 tuple의 authority에 위임 코드를 넣고 nonce를 올린다. 잘못된 tuple은 건너뛴다.
+/// authority에 위임이 아닌 코드가 있으면(컨트랙트 계정) 건너뛴다. 0 주소로 위임하면 위임 코드를 지운다.
+fn apply_authorizations(tx: &SetCodeTransaction, tx_hash: H256, ctx: &RpcContext) {
+    for (index, report) in tx.verify_authorizations(ctx.node.config.chain_id).iter().enumerate() {
+        let authority = match (report.valid, report.authority) {
+            (true, Some(authority)) => authority,
+            _ => {
+                warn!("[JSON_RPC] tx={:?} authorization[{}] invalid: {}", tx_hash, index, report.reason.clone().unwrap_or_default());
+                continue;
             }
+        };
+        let nonce = ctx.node.state.nonce(&authority, BlockTag::Pending).unwrap_or_default();
+        if report.nonce != nonce {
+            warn!("[JSON_RPC] tx={:?} authorization[{}] authority={:?} nonce mismatch: have {} want {}", tx_hash, index, authority, report.nonce, nonce);
+            continue;
         }
-        // let res = RpcStringResponse::new(self.0.id, &result);
-        // serde_json::to_string::<RpcStringResponse>(&res).unwrap()
+        let code = ctx.node.state.code(&authority, BlockTag::Pending).unwrap_or_default();
+        if !code.0.is_empty() && !is_delegation(&code) {
+            warn!("[JSON_RPC] tx={:?} authorization[{}] authority={:?} has code that is not a delegation", tx_hash, index, authority);
+            continue;
+        }
+        info!("[JSON_RPC] tx={:?} authorization[{}] authority={:?} delegated to {:?}", tx_hash, index, authority, report.address);
+        let code = delegation_code(&report.address);
+        ctx.node.state.update(authority, |account| {
+            account.code = code;
+            account.nonce = account.nonce + 1;
+        });
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,34 @@
+use ethereum_types::U256;
+use serde::Deserialize;
+use crate::logs::LogFilter;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
+use crate::subscriptions::SubscriptionKind;
+
+/// `[kind, filter]`
+/// `kind`는 "newHeads", "logs", "newPendingTransactions" 중 하나이고, `filter`는 "logs"에만 쓴다.
+#[derive(Deserialize)]
+pub struct EthSubscribeParams {
+    pub kind: String,
+    #[serde(default)]
+    pub filter: Option<LogFilter>,
+}
+
+/// WebSocket 연결에서만 쓸 수 있다. 알림은 `eth_subscription` 메서드로 보낸다.
+pub struct EthSubscribe;
+
+impl RpcCall for EthSubscribe {
+    type Params = EthSubscribeParams;
+    type Result = U256;
+
+    fn receive(&self, params: EthSubscribeParams, ctx: &RpcContext) -> Result<U256, RpcError> {
+        let subscriber = ctx.subscriber.ok_or_else(|| RpcError::method_not_supported("notifications not supported"))?;
+        let kind = match params.kind.as_str() {
+            "newHeads" => SubscriptionKind::NewHeads,
+            "logs" => SubscriptionKind::Logs(params.filter.unwrap_or_default()),
+            "newPendingTransactions" => SubscriptionKind::NewPendingTransactions,
+            kind => return Err(RpcError::invalid_params(format!("unsupported subscription '{}'", kind).as_str())),
+        };
+        Ok(ctx.node.subscriptions.subscribe(kind, subscriber))
+    }
+}
@@ -0,0 +1,16 @@
+use ethereum_types::U256;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
+
+/// `[filter_id]`
+/// 없거나 이미 지워진 필터이면 false
+pub struct EthUninstallFilter;
+
+impl RpcCall for EthUninstallFilter {
+    type Params = (U256,);
+    type Result = bool;
+
+    fn receive(&self, params: (U256,), ctx: &RpcContext) -> Result<bool, RpcError> {
+        Ok(ctx.node.filters.uninstall(&params.0))
+    }
+}
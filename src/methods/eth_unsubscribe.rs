@@ -0,0 +1,17 @@
+use ethereum_types::U256;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
+
+/// `[subscription_id]`
+/// 같은 연결에서 만든 구독이 아니면 false
+pub struct EthUnsubscribe;
+
+impl RpcCall for EthUnsubscribe {
+    type Params = (U256,);
+    type Result = bool;
+
+    fn receive(&self, params: (U256,), ctx: &RpcContext) -> Result<bool, RpcError> {
+        let subscriber = ctx.subscriber.ok_or_else(|| RpcError::method_not_supported("notifications not supported"))?;
+        Ok(ctx.node.subscriptions.unsubscribe(&params.0, subscriber.connection_id))
+    }
+}
@@ -0,0 +1,26 @@
+use ethereum_types::U64;
+use serde::Deserialize;
+use crate::methods::{RpcCall, RpcContext};
+use crate::miner;
+use crate::response::RpcError;
+
+/// `[timestamp]`
+#[derive(Deserialize)]
+pub struct EvmMineParams {
+    #[serde(default)]
+    pub timestamp: Option<U64>,
+}
+
+/// 기다리는 트랜잭션을 모두 넣어 블록 하나를 바로 만든다. 모든 mining 모드에서 쓸 수 있다.
+/// Hardhat, Anvil처럼 "0x0"을 돌려준다.
+pub struct EvmMine;
+
+impl RpcCall for EvmMine {
+    type Params = EvmMineParams;
+    type Result = String;
+
+    fn receive(&self, params: EvmMineParams, ctx: &RpcContext) -> Result<String, RpcError> {
+        miner::mine(ctx.node, params.timestamp.map(|timestamp| timestamp.as_u64()));
+        Ok("0x0".to_string())
+    }
+}
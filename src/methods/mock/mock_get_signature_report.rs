@@ -0,0 +1,17 @@
+use ethereum_types::H256;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
+use crate::signature_report::SignatureReport;
+
+/// eth_sendRawTransaction으로 받은 트랜잭션의 서명 검사 결과
+/// 받은 적 없는 해시이면 null
+pub struct MockGetSignatureReport;
+
+impl RpcCall for MockGetSignatureReport {
+    type Params = (H256,);
+    type Result = Option<SignatureReport>;
+
+    fn receive(&self, params: (H256,), ctx: &RpcContext) -> Result<Option<SignatureReport>, RpcError> {
+        Ok(ctx.node.signature_reports.get(&params.0))
+    }
+}
@@ -0,0 +1,18 @@
+use ethereum_types::Address;
+use crate::logs::ScriptedLog;
+use crate::methods::{RpcCall, RpcContext};
+use crate::response::RpcError;
+
+/// `[address, [{"topics": [...], "data": "0x..."}, ...]]`
+/// `address`로 가는 트랜잭션이 블록에 들어갈 때 남길 로그를 정한다. 빈 배열이면 지운다.
+pub struct MockSetLogs;
+
+impl RpcCall for MockSetLogs {
+    type Params = (Address, Vec<ScriptedLog>);
+    type Result = bool;
+
+    fn receive(&self, params: (Address, Vec<ScriptedLog>), ctx: &RpcContext) -> Result<bool, RpcError> {
+        ctx.node.log_scripts.set(params.0, params.1);
+        Ok(true)
+    }
+}
@@ -0,0 +1,2 @@
+pub mod mock_get_signature_report;
+pub mod mock_set_logs;
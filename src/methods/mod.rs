@@ -1,10 +1,16 @@
 use std::sync::Mutex;
 use hub_channel::channel::Channel;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use crate::node::Node;
+use crate::response::RpcError;
+use crate::subscriptions::Subscriber;
 
 pub mod web3;
 pub mod net_version;
 pub mod eth_protocol_version;
 pub mod eth_gas_price;
+pub mod eth_accounts;
 pub mod eth_block_number;
 pub mod eth_get_balance;
 pub mod eth_get_transaction_count;
@@ -14,12 +20,44 @@ pub mod eth_get_block_by_hash;
 pub mod eth_chain_id;
 pub mod eth_get_block_by_number;
 pub mod eth_call;
+pub mod eth_get_transaction_by_hash;
+pub mod eth_get_transaction_by_block_hash_and_index;
+pub mod eth_get_transaction_by_block_number_and_index;
+pub mod eth_get_transaction_receipt;
+pub mod eth_get_code;
+pub mod eth_get_logs;
+pub mod eth_new_filter;
+pub mod eth_new_block_filter;
+pub mod eth_new_pending_transaction_filter;
+pub mod eth_uninstall_filter;
+pub mod eth_get_filter_changes;
+pub mod eth_get_filter_logs;
+pub mod eth_subscribe;
+pub mod eth_unsubscribe;
+pub mod eth_get_storage_at;
 pub mod debug;
+pub mod evm;
+pub mod mock;
+pub mod rpc_modules;
+pub mod registry;
+
+/// 메서드가 호출될 때 함께 전달되는 정보
+/// - `node`      - 요청을 받은 노드
+/// - `channel`   - hub 채널. basic_http listener를 거치지 않은 요청(MockServer 등)에는 없다.
+/// - `subscriber` - 요청이 들어온 WebSocket 연결. eth_subscribe 알림을 받을 곳이며, HTTP 요청에는 없다.
+pub struct RpcContext<'a> {
+    pub node: &'a Node,
+    pub channel: Option<&'a Mutex<Channel>>,
+    pub subscriber: Option<&'a Subscriber>,
+}
 
 /// RPC 메서드들에 대한 공통 특성
 pub trait RpcCall {
-    /// 노드가 해당 RPC를 호출할 때 사용하는 메서드
-    fn call(&self) -> String;
+    /// 요청의 `params` 배열을 해석한 타입
+    type Params: DeserializeOwned;
+    /// 응답의 `result`로 직렬화될 타입
+    type Result: Serialize;
+
     /// 노드가 해당 RPC를 요청받을 때 사용하는 메서드
-    fn receive(&self, ch: &Mutex<Channel>) -> String;
-}
\ No newline at end of file
+    fn receive(&self, params: Self::Params, ctx: &RpcContext) -> Result<Self::Result, RpcError>;
+}
@@ -0,0 +1,276 @@
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex};
+use log::error;
+use serde_json::Value;
+use crate::methods::{RpcCall, RpcContext};
+use crate::methods::debug::debug_raw_transaction::DebugRawTransaction;
+use crate::methods::mock::mock_get_signature_report::MockGetSignatureReport;
+use crate::methods::mock::mock_set_logs::MockSetLogs;
+use crate::methods::debug::debug_recovered_panics::DebugRecoveredPanics;
+use crate::methods::eth_accounts::EthAccounts;
+use crate::methods::eth_block_number::EthBlockNumber;
+use crate::methods::eth_call::EthCall;
+use crate::methods::eth_chain_id::EthChainId;
+use crate::methods::eth_estimate_gas::EthEstimateGas;
+use crate::methods::eth_gas_price::EthGasPrice;
+use crate::methods::eth_get_balance::EthGetBalance;
+use crate::methods::eth_get_block_by_hash::EthGetBlockByHash;
+use crate::methods::eth_get_block_by_number::EthGetBlockByNumber;
+use crate::methods::eth_get_transaction_count::EthGetTransactionCount;
+use crate::methods::eth_protocol_version::EthProtocolVersion;
+use crate::methods::eth_send_raw_transaction::EthSendRawTransaction;
+use crate::methods::eth_get_transaction_by_hash::EthGetTransactionByHash;
+use crate::methods::eth_get_transaction_by_block_hash_and_index::EthGetTransactionByBlockHashAndIndex;
+use crate::methods::eth_get_transaction_by_block_number_and_index::EthGetTransactionByBlockNumberAndIndex;
+use crate::methods::eth_get_transaction_receipt::EthGetTransactionReceipt;
+use crate::methods::eth_get_code::EthGetCode;
+use crate::methods::eth_get_logs::EthGetLogs;
+use crate::methods::eth_new_filter::EthNewFilter;
+use crate::methods::eth_new_block_filter::EthNewBlockFilter;
+use crate::methods::eth_new_pending_transaction_filter::EthNewPendingTransactionFilter;
+use crate::methods::eth_uninstall_filter::EthUninstallFilter;
+use crate::methods::eth_get_filter_changes::EthGetFilterChanges;
+use crate::methods::eth_get_filter_logs::EthGetFilterLogs;
+use crate::methods::eth_subscribe::EthSubscribe;
+use crate::methods::eth_unsubscribe::EthUnsubscribe;
+use crate::methods::evm::evm_mine::EvmMine;
+use crate::methods::eth_get_storage_at::EthGetStorageAt;
+use crate::methods::net_version::NetVersion;
+use crate::methods::rpc_modules::RpcModules;
+use crate::methods::web3::{Web3ClientVersion, Web3Sha3};
+use crate::response::RpcError;
+
+/// 이더리움 JSON-RPC 명세에 있는 메서드 목록
+/// 등록되지 않은 메서드를 보고할 때 사용한다.
+pub const STANDARD_METHODS: [&str; 47] = [
+    "web3_clientVersion", "web3_sha3",
+    "net_version", "net_peerCount", "net_listening",
+    "eth_protocolVersion", "eth_syncing", "eth_coinbase", "eth_mining", "eth_hashrate",
+    "eth_gasPrice", "eth_accounts", "eth_blockNumber", "eth_getBalance", "eth_getStorageAt",
+    "eth_getTransactionCount", "eth_getBlockTransactionCountByHash", "eth_getBlockTransactionCountByNumber",
+    "eth_getUncleCountByBlockHash", "eth_getUncleCountByBlockNumber", "eth_getCode",
+    "eth_sign", "eth_signTransaction", "eth_sendTransaction", "eth_sendRawTransaction",
+    "eth_call", "eth_estimateGas", "eth_getBlockByHash", "eth_getBlockByNumber",
+    "eth_getTransactionByHash", "eth_getTransactionByBlockHashAndIndex", "eth_getTransactionByBlockNumberAndIndex",
+    "eth_getTransactionReceipt", "eth_getUncleByBlockHashAndIndex", "eth_getUncleByBlockNumberAndIndex",
+    "eth_newFilter", "eth_newBlockFilter", "eth_newPendingTransactionFilter", "eth_uninstallFilter",
+    "eth_getFilterChanges", "eth_getFilterLogs", "eth_getLogs",
+    "eth_getWork", "eth_submitWork", "eth_submitHashrate",
+    "eth_chainId", "eth_feeHistory",
+];
+
+/// 레지스트리에 등록되는 핸들러 객체
+/// `RpcCall`을 구현한 타입은 모두 자동으로 `RpcHandler`가 된다.
+pub trait RpcHandler: Send + Sync {
+    fn handle(&self, params: Value, ctx: &RpcContext) -> Result<Value, RpcError>;
+}
+
+impl<M: RpcCall + Send + Sync> RpcHandler for M {
+    fn handle(&self, params: Value, ctx: &RpcContext) -> Result<Value, RpcError> {
+        let params = serde_json::from_value::<M::Params>(params)
+            .map_err(|e| RpcError::invalid_params(&e.to_string()))?;
+        let result = self.receive(params, ctx)?;
+        serde_json::to_value(result).map_err(|e| RpcError::internal_error(&e.to_string()))
+    }
+}
+
+/// 클로저로 등록하는 사용자 정의 메서드
+struct FnHandler<F>(F);
+
+impl<F> RpcHandler for FnHandler<F>
+    where F: Fn(Value, &RpcContext) -> Result<Value, RpcError> + Send + Sync {
+    fn handle(&self, params: Value, ctx: &RpcContext) -> Result<Value, RpcError> {
+        (self.0)(params, ctx)
+    }
+}
+
+/// 메서드 이름과 핸들러 객체를 연결하는 레지스트리
+/// # Example
+/// ```ignore
+/// let mut registry = MethodRegistry::with_default_methods();
+/// registry.register_fn("wallet_ping", |_params, _ctx| Ok(serde_json::json!("pong")));
+/// let node = Node::new(Config::default(), registry);
+/// ```
+pub struct MethodRegistry {
+    handlers: HashMap<String, Arc<dyn RpcHandler>>,
+    disabled_namespaces: HashSet<String>,
+    /// 메서드별로 복구한 panic 횟수
+    recovered_panics: Mutex<BTreeMap<String, u64>>,
+}
+
+impl MethodRegistry {
+    pub fn new() -> Self {
+        Self {
+            handlers: HashMap::new(),
+            disabled_namespaces: HashSet::new(),
+            recovered_panics: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// 기본 제공되는 메서드가 모두 등록된 레지스트리
+    pub fn with_default_methods() -> Self {
+        let mut registry = Self::new();
+        registry.register("web3_clientVersion", Web3ClientVersion);
+        registry.register("web3_sha3", Web3Sha3);
+        registry.register("net_version", NetVersion);
+        registry.register("eth_protocolVersion", EthProtocolVersion);
+        registry.register("eth_gasPrice", EthGasPrice);
+        registry.register("eth_accounts", EthAccounts);
+        registry.register("eth_blockNumber", EthBlockNumber);
+        registry.register("eth_getBalance", EthGetBalance);
+        registry.register("eth_getTransactionCount", EthGetTransactionCount);
+        registry.register("eth_getCode", EthGetCode);
+        registry.register("eth_getStorageAt", EthGetStorageAt);
+        registry.register("eth_sendRawTransaction", EthSendRawTransaction);
+        registry.register("eth_getTransactionByHash", EthGetTransactionByHash);
+        registry.register("eth_getTransactionByBlockHashAndIndex", EthGetTransactionByBlockHashAndIndex);
+        registry.register("eth_getTransactionByBlockNumberAndIndex", EthGetTransactionByBlockNumberAndIndex);
+        registry.register("eth_getTransactionReceipt", EthGetTransactionReceipt);
+        registry.register("eth_getLogs", EthGetLogs);
+        registry.register("eth_newFilter", EthNewFilter);
+        registry.register("eth_newBlockFilter", EthNewBlockFilter);
+        registry.register("eth_newPendingTransactionFilter", EthNewPendingTransactionFilter);
+        registry.register("eth_uninstallFilter", EthUninstallFilter);
+        registry.register("eth_getFilterChanges", EthGetFilterChanges);
+        registry.register("eth_getFilterLogs", EthGetFilterLogs);
+        registry.register("eth_subscribe", EthSubscribe);
+        registry.register("eth_unsubscribe", EthUnsubscribe);
+        registry.register("eth_call", EthCall);
+        registry.register("eth_estimateGas", EthEstimateGas);
+        registry.register("eth_getBlockByNumber", EthGetBlockByNumber);
+        registry.register("eth_getBlockByHash", EthGetBlockByHash);
+        registry.register("eth_chainId", EthChainId);
+        registry.register("debug_rawTransaction", DebugRawTransaction);
+        registry.alias("debugRawTransaction", "debug_rawTransaction");
+        registry.register("debug_recoveredPanics", DebugRecoveredPanics);
+        registry.register("mock_getSignatureReport", MockGetSignatureReport);
+        registry.register("mock_setLogs", MockSetLogs);
+        registry.register("evm_mine", EvmMine);
+        registry.register("rpc_modules", RpcModules);
+        registry
+    }
+
+    /// 메서드를 등록한다. 같은 이름이 이미 있으면 덮어쓴다.
+    pub fn register<H: RpcHandler + 'static>(&mut self, name: &str, handler: H) {
+        self.handlers.insert(name.to_string(), Arc::new(handler));
+    }
+
+    /// 클로저를 메서드로 등록한다.
+    pub fn register_fn<F>(&mut self, name: &str, f: F)
+        where F: Fn(Value, &RpcContext) -> Result<Value, RpcError> + Send + Sync + 'static {
+        self.register(name, FnHandler(f));
+    }
+
+    /// 이미 등록된 메서드를 다른 이름으로도 부를 수 있게 한다.
+    pub fn alias(&mut self, alias: &str, name: &str) {
+        if let Some(handler) = self.handlers.get(name).cloned() {
+            self.handlers.insert(alias.to_string(), handler);
+        }
+    }
+
+    pub fn unregister(&mut self, name: &str) -> bool {
+        self.handlers.remove(name).is_some()
+    }
+
+    pub fn disable_namespace(&mut self, namespace: &str) {
+        self.disabled_namespaces.insert(namespace.to_string());
+    }
+
+    pub fn enable_namespace(&mut self, namespace: &str) {
+        self.disabled_namespaces.remove(namespace);
+    }
+
+    /// 메서드 이름에 해당하는 핸들러. 꺼진 네임스페이스의 메서드는 찾지 않는다.
+    pub fn get(&self, name: &str) -> Option<Arc<dyn RpcHandler>> {
+        if let Some(namespace) = namespace_of(name) {
+            if self.disabled_namespaces.contains(namespace) {
+                return None;
+            }
+        }
+        self.handlers.get(name).cloned()
+    }
+
+    /// 메서드를 찾아 호출한다.
+    /// 메서드 안에서 panic이 나면 서버 스레드를 잃지 않도록 잡아서 -32603 오류로 바꾸고 횟수를 센다.
+    pub fn call(&self, name: &str, params: Value, ctx: &RpcContext) -> Result<Value, RpcError> {
+        let handler = match self.get(name) {
+            Some(handler) => handler,
+            None => return Err(RpcError::method_not_found(name)),
+        };
+        match std::panic::catch_unwind(AssertUnwindSafe(|| handler.handle(params, ctx))) {
+            Ok(result) => result,
+            Err(payload) => {
+                let message = panic_message(&payload);
+                let count = self.record_panic(name);
+                error!("[JSON_RPC] method='{}' panicked ({} times so far): {}", name, count, message);
+                Err(RpcError::internal_error(&message))
+            }
+        }
+    }
+
+    fn record_panic(&self, name: &str) -> u64 {
+        let mut recovered_panics = self.recovered_panics.lock().unwrap_or_else(|e| e.into_inner());
+        let count = recovered_panics.entry(name.to_string()).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    /// 지금까지 메서드별로 복구한 panic 횟수
+    pub fn recovered_panics(&self) -> BTreeMap<String, u64> {
+        self.recovered_panics.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// 호출 가능한 메서드 이름 목록 (정렬됨)
+    pub fn methods(&self) -> Vec<String> {
+        let mut methods: Vec<String> = self.handlers.keys()
+            .filter(|name| self.get(name).is_some())
+            .cloned()
+            .collect();
+        methods.sort();
+        methods
+    }
+
+    /// 켜져 있는 네임스페이스와 버전 (`rpc_modules`의 결과)
+    pub fn modules(&self) -> BTreeMap<String, String> {
+        let mut modules = BTreeMap::new();
+        for name in self.methods().iter() {
+            if let Some(namespace) = namespace_of(name) {
+                modules.insert(namespace.to_string(), "1.0".to_string());
+            }
+        }
+        modules
+    }
+
+    /// 명세에는 있지만 등록되지 않은 메서드 목록
+    pub fn unsupported(&self) -> Vec<&'static str> {
+        STANDARD_METHODS.iter()
+            .filter(|name| self.get(name).is_none())
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for MethodRegistry {
+    fn default() -> Self {
+        Self::with_default_methods()
+    }
+}
+
+/// `eth_getBalance` -> `eth`
+/// 밑줄이 없는 이름(`debugRawTransaction` 등)은 네임스페이스가 없다.
+pub fn namespace_of(name: &str) -> Option<&str> {
+    name.find('_').map(|index| &name[..index])
+}
+
+/// panic 값에서 메시지를 꺼낸다. `panic!`은 `&str` 혹은 `String`을 담는다.
+fn panic_message(payload: &Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+    "unknown panic".to_string()
+}
@@ -0,0 +1,18 @@
+use std::collections::BTreeMap;
+use crate::methods::{RpcCall, RpcContext};
+use crate::request::EmptyParams;
+use crate::response::RpcError;
+
+/// 켜져 있는 네임스페이스 목록을 제공하는 RPC
+/// # Example
+/// * {"eth":"1.0","net":"1.0","rpc":"1.0","web3":"1.0"}
+pub struct RpcModules;
+
+impl RpcCall for RpcModules {
+    type Params = EmptyParams;
+    type Result = BTreeMap<String, String>;
+
+    fn receive(&self, _params: EmptyParams, ctx: &RpcContext) -> Result<BTreeMap<String, String>, RpcError> {
+        Ok(ctx.node.registry.modules())
+    }
+}
@@ -0,0 +1,271 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use crypto::hash::keccak256;
+use ethereum_types::{Address, Bloom, H256, H64, U256, U64};
+use log::{info, warn};
+use rlp::RlpStream;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use crate::blob_transaction::{blob_gas_price, calc_excess_blob_gas, MAX_BLOB_GAS_PER_BLOCK};
+use crate::block::{Block, BlockHeader, BlockTransactions, SealedBlock};
+use crate::bytes::Bytes;
+use crate::node::Node;
+use crate::logs::{logs_bloom, LogFilter};
+use crate::receipt::{Log, Receipt};
+use crate::transaction_pool::PooledTransaction;
+use crate::trie::ordered_trie_root;
+use crate::typed_transaction::TypedTransaction;
+
+pub const BLOCK_GAS_LIMIT: u64 = 30_000_000;
+const MINER_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// 블록을 만드는 시점
+/// - `auto`        - 트랜잭션을 받을 때마다 바로 블록을 만든다.
+/// - `interval`    - `block_time`초마다 블록을 만든다. 트랜잭션이 없어도 만든다.
+/// - `manual`      - `evm_mine`을 부를 때만 만든다.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all="lowercase")]
+pub enum MiningMode {
+    Auto,
+    Interval,
+    Manual,
+}
+
+impl Default for MiningMode {
+    fn default() -> Self {
+        MiningMode::Auto
+    }
+}
+
+impl FromStr for MiningMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(MiningMode::Auto),
+            "interval" => Ok(MiningMode::Interval),
+            "manual" => Ok(MiningMode::Manual),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for MiningMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MiningMode::Auto => write!(f, "auto"),
+            MiningMode::Interval => write!(f, "interval"),
+            MiningMode::Manual => write!(f, "manual"),
+        }
+    }
+}
+
+/// 0번 블록. 트랜잭션 없이 genesis 상태만 가진다.
+pub fn genesis(state_root: H256, base_fee_per_gas: U256) -> SealedBlock {
+    let header = BlockHeader {
+        state_root,
+        timestamp: now(),
+        ..empty_header(0, base_fee_per_gas)
+    };
+    seal(header, vec![], &[])
+}
+
+/// 기다리는 트랜잭션을 받은 순서대로 넣어 다음 블록을 만든다.
+/// `BLOCK_GAS_LIMIT`이나 `MAX_BLOB_GAS_PER_BLOCK`을 넘게 되면 거기서 멈추고, 나머지는 다음 블록으로 미룬다.
+/// `timestamp`가 없거나 부모 블록보다 이르면 부모 블록 다음 초로 맞춘다.
+/// EVM을 실행하지 않으므로 트랜잭션이 쓴 gas는 intrinsic gas로 보고, receipt도 함께 만든다.
+pub fn mine(node: &Node, timestamp: Option<u64>) -> SealedBlock {
+    let base_fee_per_gas = U256::from(node.config.gas_price);
+    let block = node.chain.seal(|parent, pending| {
+        let (transactions, remaining) = select_transactions(node, pending);
+        if !remaining.is_empty() {
+            info!("[MINER] block is full, {} transactions wait for the next block", remaining.len());
+        }
+        let encoded: Vec<Vec<u8>> = transactions.iter().map(|tx| tx.tx.encode()).collect();
+        let number = parent.number() + 1;
+        let excess_blob_gas = calc_excess_blob_gas(&parent.header);
+        let mut receipts = build_receipts(node, &transactions, number, base_fee_per_gas, blob_gas_price(excess_blob_gas));
+        let mut logs_bloom = Bloom::zero();
+        for receipt in receipts.iter() {
+            logs_bloom.accrue_bloom(&receipt.logs_bloom);
+        }
+        let header = BlockHeader {
+            parent_hash: parent.hash,
+            state_root: node.state.commit(number),
+            transactions_root: ordered_trie_root(&encoded),
+            receipts_root: ordered_trie_root(receipts.iter().map(|receipt| receipt.encode())),
+            logs_bloom,
+            gas_used: receipts.last().map(|receipt| receipt.cumulative_gas_used.as_u64()).unwrap_or_default(),
+            timestamp: timestamp.unwrap_or_else(now).max(parent.header.timestamp + 1),
+            blob_gas_used: transactions.iter().map(|tx| tx.tx.blob_gas_used()).sum(),
+            excess_blob_gas,
+            ..empty_header(number, base_fee_per_gas)
+        };
+        let block = seal(header, transactions.iter().map(|tx| tx.hash).collect(), &encoded);
+        let mut logs = vec![];
+        for receipt in receipts.iter_mut() {
+            receipt.block_hash = block.hash;
+            for log in receipt.logs.iter_mut() {
+                log.block_hash = block.hash;
+            }
+            logs.extend(receipt.logs.iter().cloned());
+            node.receipts.insert(receipt.clone());
+        }
+        node.logs.insert(number, block.header.logs_bloom, logs);
+        (block, remaining)
+    });
+    info!("[MINER] block #{} hash={:?} transactions={} gas_used={}",
+        block.number(), block.hash, block.transactions.len(), block.header.gas_used);
+    let logs = node.logs.logs(block.number()..=block.number(), &LogFilter::default());
+    node.subscriptions.notify_block(&Block::new(&block, BlockTransactions::Hashes(block.transactions.clone())), &logs);
+    block
+}
+
+/// 블록에 넣을 트랜잭션과 다음 블록으로 미룰 트랜잭션의 해시.
+/// nonce 순서가 어긋나지 않도록 한도를 넘는 트랜잭션부터는 모두 미룬다.
+fn select_transactions(node: &Node, pending: Vec<H256>) -> (Vec<PooledTransaction>, Vec<H256>) {
+    let mut transactions = vec![];
+    let mut remaining = vec![];
+    let mut gas_used = 0u64;
+    let mut blob_gas_used = 0u64;
+    for hash in pending {
+        let tx = match node.transactions.get(&hash) {
+            Some(tx) => tx,
+            None => continue,
+        };
+        let full = !remaining.is_empty()
+            || gas_used + tx.tx.intrinsic_gas() > BLOCK_GAS_LIMIT
+            || blob_gas_used + tx.tx.blob_gas_used() > MAX_BLOB_GAS_PER_BLOCK;
+        if full {
+            remaining.push(hash);
+            continue;
+        }
+        gas_used += tx.tx.intrinsic_gas();
+        blob_gas_used += tx.tx.blob_gas_used();
+        transactions.push(tx);
+    }
+    (transactions, remaining)
+}
+
+/// 블록에 들어갈 트랜잭션의 receipt. 블록 해시는 블록을 만든 뒤에 채운다.
+/// 로그는 `node.log_scripts`에 정해 둔 것을 남긴다.
+fn build_receipts(node: &Node, transactions: &[PooledTransaction], number: u64, base_fee_per_gas: U256, blob_gas_price: U256) -> Vec<Receipt> {
+    let mut cumulative_gas_used = 0;
+    let mut log_index = 0u64;
+    transactions.iter().enumerate().map(|(index, tx)| {
+        let gas_used = tx.tx.intrinsic_gas();
+        cumulative_gas_used += gas_used;
+        let contract_address = tx.tx.contract_address(&tx.from);
+        let logs: Vec<Log> = tx.tx.to().or(contract_address)
+            .map(|address| node.log_scripts.get(&address).into_iter().map(|script| {
+                log_index += 1;
+                Log {
+                    address,
+                    topics: script.topics,
+                    data: script.data,
+                    block_hash: H256::zero(),
+                    block_number: number.into(),
+                    transaction_hash: tx.hash,
+                    transaction_index: (index as u64).into(),
+                    log_index: (log_index - 1).into(),
+                    removed: false,
+                }
+            }).collect())
+            .unwrap_or_default();
+        let blob_gas_used = match &tx.tx {
+            TypedTransaction::Blob(btx) => Some(btx.blob_gas_used()),
+            _ => None,
+        };
+        Receipt {
+            transaction_hash: tx.hash,
+            transaction_index: (index as u64).into(),
+            block_hash: H256::zero(),
+            block_number: number.into(),
+            from: tx.from,
+            to: tx.tx.to(),
+            cumulative_gas_used: cumulative_gas_used.into(),
+            gas_used: gas_used.into(),
+            effective_gas_price: tx.tx.effective_gas_price(Some(base_fee_per_gas)),
+            contract_address,
+            logs_bloom: logs_bloom(&logs),
+            logs,
+            tx_type: tx.tx.tx_type().into(),
+            status: U64::one(),
+            blob_gas_used: blob_gas_used.map(U64::from),
+            blob_gas_price: blob_gas_used.map(|_| blob_gas_price),
+        }
+    }).collect()
+}
+
+/// 노드가 멈출 때까지 `block_time`초마다 블록을 만든다.
+pub fn run_interval(node: &Node) {
+    if node.config.block_time == 0 {
+        warn!("[MINER] interval mining needs block_time > 0");
+        return;
+    }
+    let interval = Duration::from_secs(node.config.block_time);
+    info!("[MINER] mining a block every {:?}", interval);
+    let mut next_block = Instant::now() + interval;
+    while !node.lifecycle.is_shutting_down() {
+        if Instant::now() >= next_block {
+            mine(node, None);
+            next_block += interval;
+        }
+        std::thread::sleep(MINER_POLL_INTERVAL);
+    }
+}
+
+fn empty_header(number: u64, base_fee_per_gas: U256) -> BlockHeader {
+    let empty_root = ordered_trie_root(Vec::<Vec<u8>>::new());
+    BlockHeader {
+        parent_hash: H256::zero(),
+        ommers_hash: keccak256(&rlp::EMPTY_LIST_RLP),
+        beneficiary: Address::zero(),
+        state_root: empty_root,
+        transactions_root: empty_root,
+        receipts_root: empty_root,
+        logs_bloom: Bloom::zero(),
+        difficulty: U256::zero(),
+        number,
+        gas_limit: BLOCK_GAS_LIMIT,
+        gas_used: 0,
+        timestamp: 0,
+        extra_data: Bytes::default(),
+        mix_hash: H256::zero(),
+        nonce: H64::zero(),
+        base_fee_per_gas,
+        withdrawals_root: empty_root,
+        blob_gas_used: 0,
+        excess_blob_gas: 0,
+        parent_beacon_block_root: H256::zero(),
+        // EIP-7685: 요청이 없으면 sha256("")
+        requests_hash: H256::from_slice(Sha256::digest(&[]).as_slice()),
+    }
+}
+
+/// 헤더 해시와 블록 크기를 계산한다. `encoded`는 canonical form으로 인코딩한 트랜잭션이다.
+fn seal(header: BlockHeader, transactions: Vec<H256>, encoded: &[Vec<u8>]) -> SealedBlock {
+    let mut stream = RlpStream::new_list(4);
+    stream.append(&header);
+    stream.begin_list(encoded.len());
+    for tx in encoded {
+        // legacy 트랜잭션은 리스트 그대로, typed 트랜잭션은 바이트 문자열로 넣는다.
+        match tx.first() {
+            Some(&first) if first >= 0xc0 => stream.append_raw(tx, 1),
+            _ => stream.append(tx),
+        };
+    }
+    stream.begin_list(0);
+    stream.begin_list(0);
+    SealedBlock {
+        hash: header.hash(),
+        size: stream.out().len() as u64,
+        header,
+        transactions,
+    }
+}
+
+fn now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
@@ -0,0 +1,167 @@
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use ethereum_types::{H256, U256};
+use log::{info, warn};
+use serde::Serialize;
+use serde_json::Value;
+use crate::blockchain::Blockchain;
+use crate::bytes::Bytes;
+use crate::config::Config;
+use crate::filters::FilterManager;
+use crate::lifecycle::Lifecycle;
+use crate::logs::{LogScripts, LogStore};
+use crate::methods::registry::MethodRegistry;
+use crate::miner;
+use crate::receipt::ReceiptStore;
+use crate::recorder::Recorder;
+use crate::request::RpcId;
+use crate::signature_report::SignatureReports;
+use crate::subscriptions::SubscriptionManager;
+use crate::transaction_pool::TransactionPool;
+use crate::world_state::WorldState;
+
+/// 서버 하나가 가지는 상태
+/// HTTP listener, MockServer 등 전송 계층은 모두 Node를 통해 메서드를 부른다.
+/// # [Fields]
+/// - `config: Config`              - 노드 설정
+/// - `registry: MethodRegistry`    - 호출 가능한 메서드
+/// - `history: History`            - 지금까지 받은 요청과 트랜잭션
+/// - `transactions: TransactionPool` - 해석한 트랜잭션 (해시로 찾는다)
+/// - `state: WorldState`           - 계정별 잔액, nonce, 코드, 스토리지
+/// - `chain: Blockchain`           - 만들어진 블록과 다음 블록에 들어갈 트랜잭션
+/// - `receipts: ReceiptStore`      - 블록에 들어간 트랜잭션의 receipt (해시로 찾는다)
+/// - `logs: LogStore`              - 블록에 들어간 트랜잭션이 남긴 로그 (블록 번호로 찾는다)
+/// - `log_scripts: LogScripts`     - 계정별로 트랜잭션이 남길 로그
+/// - `filters: FilterManager`      - eth_newFilter 계열 메서드로 설치한 필터
+/// - `subscriptions: SubscriptionManager` - WebSocket 연결에서 eth_subscribe로 만든 구독
+/// - `recorder: Option<Recorder>`  - 받은 요청과 응답을 남기는 JSONL cassette. `record_path`가 있을 때만 쓴다.
+/// - `signature_reports: SignatureReports` - 받은 트랜잭션의 서명 검사 결과
+/// - `lifecycle: Lifecycle`        - 종료 상태와 처리 중인 요청 수
+pub struct Node {
+    pub config: Config,
+    pub registry: MethodRegistry,
+    pub history: History,
+    pub transactions: TransactionPool,
+    pub state: WorldState,
+    pub chain: Blockchain,
+    pub receipts: ReceiptStore,
+    pub logs: LogStore,
+    pub log_scripts: LogScripts,
+    pub filters: FilterManager,
+    pub subscriptions: SubscriptionManager,
+    pub recorder: Option<Recorder>,
+    pub signature_reports: SignatureReports,
+    pub lifecycle: Lifecycle,
+}
+
+static GLOBAL_NODE: OnceLock<Node> = OnceLock::new();
+
+impl Node {
+    pub fn new(config: Config, registry: MethodRegistry) -> Self {
+        let state = WorldState::new(config.default_balance, &config.alloc);
+        let chain = Blockchain::new(miner::genesis(state.state_root(), U256::from(config.gas_price)));
+        let filters = FilterManager::new(Duration::from_secs(config.filter_timeout), config.max_filters);
+        let recorder = config.record_path.as_ref().and_then(|path| {
+            match Recorder::open(path, config.record_max_size, config.record_max_files) {
+                Ok(recorder) => Some(recorder),
+                Err(e) => {
+                    warn!("[RECORDER] could not open {}: {}", path, e);
+                    None
+                }
+            }
+        });
+        Self {
+            config,
+            registry,
+            history: History::default(),
+            transactions: TransactionPool::default(),
+            state,
+            chain,
+            receipts: ReceiptStore::default(),
+            logs: LogStore::default(),
+            log_scripts: LogScripts::default(),
+            filters,
+            subscriptions: SubscriptionManager::default(),
+            recorder,
+            signature_reports: SignatureReports::default(),
+            lifecycle: Lifecycle::default(),
+        }
+    }
+
+    /// 노드를 프로세스 전역으로 설치한다. 이미 설치되어 있으면 `false`.
+    /// 핸들러를 함수 포인터로만 받는 basic_http listener가 이 노드를 쓴다.
+    pub fn install(self) -> bool {
+        GLOBAL_NODE.set(self).is_ok()
+    }
+
+    /// 설치된 전역 노드. 설치된 것이 없으면 기본 설정으로 만든다.
+    pub fn global() -> &'static Node {
+        GLOBAL_NODE.get_or_init(Node::default)
+    }
+
+    /// 새 요청을 거절하고, 처리 중인 요청이 끝날 때까지 `timeout`만큼 기다린다.
+    /// 모든 요청이 끝났으면 `true`.
+    pub fn shutdown(&self, timeout: Duration) -> bool {
+        self.lifecycle.begin_shutdown();
+        info!("[NODE] shutting down, waiting for {} request(s)", self.lifecycle.in_flight());
+        let drained = self.lifecycle.drain(timeout);
+        if !drained {
+            warn!("[NODE] {} request(s) still running after {:?}", self.lifecycle.in_flight(), timeout);
+        }
+        drained
+    }
+}
+
+impl Default for Node {
+    fn default() -> Self {
+        Self::new(Config::default(), MethodRegistry::with_default_methods())
+    }
+}
+
+/// 받은 요청 기록
+#[derive(Serialize, Clone, Debug)]
+pub struct ReceivedRequest {
+    pub id: Option<RpcId>,
+    pub method: String,
+    pub params: Value,
+    #[serde(rename="peerAddr")]
+    pub peer_addr: String,
+}
+
+/// 받은 트랜잭션 기록
+#[derive(Serialize, Clone, Debug)]
+pub struct ReceivedTransaction {
+    pub hash: H256,
+    pub raw: Bytes,
+}
+
+/// 노드가 받은 요청과 트랜잭션을 순서대로 보관한다.
+/// 테스트에서 어떤 요청이 들어왔는지 확인할 때 쓴다.
+#[derive(Default)]
+pub struct History {
+    requests: Mutex<Vec<ReceivedRequest>>,
+    transactions: Mutex<Vec<ReceivedTransaction>>,
+}
+
+impl History {
+    pub fn record_request(&self, request: ReceivedRequest) {
+        self.requests.lock().unwrap_or_else(|e| e.into_inner()).push(request);
+    }
+
+    pub fn record_transaction(&self, transaction: ReceivedTransaction) {
+        self.transactions.lock().unwrap_or_else(|e| e.into_inner()).push(transaction);
+    }
+
+    pub fn requests(&self) -> Vec<ReceivedRequest> {
+        self.requests.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn transactions(&self) -> Vec<ReceivedTransaction> {
+        self.transactions.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn clear(&self) {
+        self.requests.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        self.transactions.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
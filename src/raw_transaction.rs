@@ -1,11 +1,11 @@
+use std::convert::TryFrom;
 use std::str::FromStr;
-use crypto::hash::keccak256;
-use crypto::ecdsa::public_key::PublicKey;
-use ethereum_types::{Address, H256, U256, H512};
-use log::{debug, info};
+use ethereum_types::{Address, BigEndianHash, H256, U256, H512};
 use rlp::{Encodable, Decodable, RlpStream, Rlp, DecoderError};
 use serde::{Serialize, Deserialize};
-use crate::methods::eth_chain_id::CHAIN_ID;
+use web3_signer::error::RecoveryError;
+use crate::signer::{Eip155Signer, Eip155Tx};
+use crate::typed_transaction::{append_to, decode_to};
 
 pub struct MetamaskRawTransaction {
     pub nonce: Vec<u8>,
@@ -36,16 +36,19 @@ impl Encodable for MetamaskRawTransaction {
 
 impl Decodable for MetamaskRawTransaction {
     fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 9 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
         Ok(Self {
-            nonce: rlp.val_at(0).unwrap(),
-            gas_price: rlp.val_at(1).unwrap(),
-            gas: rlp.val_at(2).unwrap(),
-            recipient: rlp.val_at(3).unwrap(),
-            value: rlp.val_at(4).unwrap(),
-            data: rlp.val_at(5).unwrap(),
-            v: rlp.val_at(6).unwrap(),
-            r: rlp.val_at(7).unwrap(),
-            s: rlp.val_at(8).unwrap(),
+            nonce: rlp.val_at(0)?,
+            gas_price: rlp.val_at(1)?,
+            gas: rlp.val_at(2)?,
+            recipient: rlp.val_at(3)?,
+            value: rlp.val_at(4)?,
+            data: rlp.val_at(5)?,
+            v: rlp.val_at(6)?,
+            r: rlp.val_at(7)?,
+            s: rlp.val_at(8)?,
         })
     }
 }
@@ -60,16 +63,16 @@ pub struct RawTransactionData {
     pub chain_id: u64,
 }
 
-impl From<&RawTransaction> for RawTransactionData {
-    fn from(rtx: &RawTransaction) -> Self {
+impl RawTransactionData {
+    pub fn new(rtx: &RawTransaction, chain_id: u64) -> Self {
         Self {
             nonce: rtx.nonce.clone(),
             gas_price: rtx.gas_price.clone(),
             gas: rtx.gas.clone(),
-            recipient: rtx.recipient.to_fixed_bytes().to_vec(),
+            recipient: rtx.to.map(|to| to.to_fixed_bytes().to_vec()).unwrap_or_default(),
             value: rtx.value.clone(),
             data: rtx.data.to_vec(),
-            chain_id: u64::from_str_radix(&CHAIN_ID.clone()[2..], 16).unwrap(),
+            chain_id,
         }
     }
 }
@@ -86,10 +89,6 @@ impl Encodable for RawTransactionData {
         s.append(&self.chain_id); // chain id
         s.append(&0u64);
         s.append(&0u64);
-        // s.append(&0u64); // empty r
-        // s.append(&0u64); // empty s
-        // s.append(&true);
-        // s.append(&self.chain_id);
     }
 }
 
@@ -98,70 +97,89 @@ pub struct RawTransaction {
     pub nonce: u64,
     pub gas_price: U256,
     pub gas: U256,
-    pub recipient: Address, // in Ethereum, it called 'to'
+    pub to: Option<Address>, // None when it creates a contract
     pub value: U256,
     pub data: Vec<u8>, // 6080 6040 ..
-    pub v: u32,
+    pub v: u64,
     pub r: Vec<u8>,
     pub s: Vec<u8>,
 }
 
 impl RawTransaction {
-    pub fn sender(&self) -> Address {
-        let rlp_tx = rlp::encode(self).to_vec();
-        let msg_digest = keccak256(rlp_tx.as_slice());
-        let r = H256::from_slice(self.r.as_slice());
-        let s = H256::from_slice(self.s.as_slice());
-        let signature = make_signature(r, s);
-        let pubkey = PublicKey::from_signature(
-            self.v as i32,
-            signature.as_fixed_bytes(),
-            msg_digest.as_fixed_bytes()).unwrap();
-        return pubkey.address();
-    }
-}
-
-impl From<MetamaskRawTransaction> for RawTransaction {
-    fn from(mrtx: MetamaskRawTransaction) -> Self {
-        let mut _str_nonce = "0".to_string();
-        if mrtx.nonce.len() != 0 {
-            // 2가 입력으로 올 때, 이것을 ASCII 2가 아닌 Integer 2로 인식해야 한다.
-            // 1 2F (=0x12F)가 입력으로 올 때, 이것을 BE 형태의 Integer 2로 인식해야 한다.
-            _str_nonce = hex::encode(mrtx.nonce.to_vec());
+    /// `v`에 들어 있는 chain id. EIP-155 이전 서명(`v` = 27, 28)에는 없다.
+    /// EIP-155: `v = chain_id * 2 + 35 + recovery_id`
+    pub fn chain_id(&self) -> Option<u64> {
+        match self.v {
+            v if v >= 35 => Some((v - 35) / 2),
+            _ => None,
         }
-        print!("{}", _str_nonce.as_str());
-        let nonce = u64::from_str_radix(_str_nonce.as_str(), 16).unwrap();
-        let mut gas_price = U256::zero();
-        if mrtx.gas_price.len() != 0 {
-            gas_price = U256::from_big_endian(mrtx.gas_price.as_ref());
-        }
-        let mut gas = U256::zero();
-        if mrtx.gas.len() != 0 {
-            gas = U256::from_big_endian(mrtx.gas.as_ref());
-        }
-        let mut recipient = Address::zero();
-        if mrtx.recipient.len() != 0 {
-            recipient = Address::from_slice(mrtx.recipient.as_ref());
-        }
-        let mut value = U256::zero();
-        if mrtx.value.len() != 0 {
-            value = U256::from_big_endian(mrtx.value.as_ref());
-        }
-        let mut _str_v = "0".to_string();
-        if mrtx.v.len() != 0 {
-            _str_v = hex::encode(mrtx.v.to_vec());
+    }
+
+    /// `v`를 recovery id(0, 1)로 바꾼다. 어느 형식에도 맞지 않는 `v`이면 `None`.
+    pub fn recovery_id(&self) -> Option<i32> {
+        match self.v {
+            27 | 28 => Some((self.v - 27) as i32),
+            v if v >= 35 => Some(((v - 35) % 2) as i32),
+            _ => None,
         }
-        info!("v: {}", _str_v);
-        let v = u32::from_str_radix(_str_v.as_str(), 16).unwrap();
+    }
 
-        Self {
-            nonce, gas_price, gas, recipient, value,
+    /// 서명할 때 쓰는 해시. 서명 필드(v, r, s)는 빼고, EIP-155이면 `[chainId, 0, 0]`을 붙인다.
+    pub fn signing_hash(&self) -> H256 {
+        let eip155_tx = Eip155Tx::from_raw_transaction(self.clone(), self.chain_id());
+        Eip155Signer::sign_from_eip155tx(eip155_tx)
+    }
+
+    pub fn sender(&self) -> Result<Address, RecoveryError> {
+        let recovery_id = self.recovery_id().ok_or(RecoveryError::InvalidSignature)?;
+        let r = H256::from_uint(&U256::from_big_endian(self.r.as_slice()));
+        let s = H256::from_uint(&U256::from_big_endian(self.s.as_slice()));
+        recover_sender(self.signing_hash(), recovery_id, r, s)
+    }
+}
+
+impl TryFrom<MetamaskRawTransaction> for RawTransaction {
+    type Error = DecoderError;
+
+    /// RLP로는 올바르지만 필드 길이가 맞지 않으면(정수나 `r`, `s`가 너무 길거나 `to`가 20바이트가 아니면) 에러를 돌려준다.
+    fn try_from(mrtx: MetamaskRawTransaction) -> Result<Self, DecoderError> {
+        let to = match mrtx.recipient.len() {
+            0 => None,
+            20 => Some(Address::from_slice(mrtx.recipient.as_ref())),
+            _ => return Err(DecoderError::RlpInvalidLength),
+        };
+        // `sender()`에서 U256으로 읽으므로 32바이트를 넘으면 안 된다.
+        if mrtx.r.len() > 32 || mrtx.s.len() > 32 {
+            return Err(DecoderError::RlpIsTooBig);
+        }
+        Ok(Self {
+            nonce: decode_u64(mrtx.nonce.as_ref())?,
+            gas_price: decode_u256(mrtx.gas_price.as_ref())?,
+            gas: decode_u256(mrtx.gas.as_ref())?,
+            to,
+            value: decode_u256(mrtx.value.as_ref())?,
             data: mrtx.data.to_vec(),
-            v,
+            v: decode_u64(mrtx.v.as_ref())?,
             r: mrtx.r.to_vec(),
             s: mrtx.s.to_vec(),
-        }
+        })
+    }
+}
+
+/// 정수 필드는 BE 형태의 바이트로 온다. 0x02는 ASCII '2'가 아닌 정수 2이고, 0x01 0x2F는 0x12F다.
+/// 빈 값은 0이다.
+fn decode_u64(bytes: &[u8]) -> Result<u64, DecoderError> {
+    if bytes.len() > 8 {
+        return Err(DecoderError::RlpIsTooBig);
     }
+    Ok(bytes.iter().fold(0u64, |value, byte| (value << 8) | *byte as u64))
+}
+
+fn decode_u256(bytes: &[u8]) -> Result<U256, DecoderError> {
+    if bytes.len() > 32 {
+        return Err(DecoderError::RlpIsTooBig);
+    }
+    Ok(U256::from_big_endian(bytes))
 }
 
 impl Encodable for RawTransaction {
@@ -171,7 +189,7 @@ impl Encodable for RawTransaction {
         s.append(&self.nonce);
         s.append(&self.gas_price);
         s.append(&self.gas);
-        s.append(&self.recipient);
+        append_to(s, &self.to);
         s.append(&self.value);
         s.append(&self.data);
         s.append(&self.v);
@@ -186,7 +204,7 @@ impl Decodable for RawTransaction {
             nonce: rlp.val_at(0)?,
             gas_price: rlp.val_at(1)?,
             gas: rlp.val_at(2)?,
-            recipient: rlp.val_at(3)?,
+            to: decode_to(&rlp.at(3)?)?,
             value: rlp.val_at(4)?,
             data: rlp.val_at(5)?,
             v: rlp.val_at(6)?,
@@ -202,7 +220,7 @@ impl Clone for RawTransaction {
             nonce: self.nonce.clone(),
             gas_price: self.gas_price.clone(),
             gas: self.gas.clone(),
-            recipient: self.recipient.clone(),
+            to: self.to.clone(),
             value: self.value.clone(),
             data: self.data.clone(),
             v: self.v.clone(),
@@ -217,4 +235,136 @@ fn make_signature(r: H256, s: H256) -> H512 {
     for c in r.as_fixed_bytes() { v512.push(c.clone()); }
     for c in s.as_fixed_bytes() { v512.push(c.clone()); }
     return H512::from_slice(v512.as_slice());
-}
\ No newline at end of file
+}
+
+/// secp256k1 n / 2
+const SECP256K1_HALF_N: &str = "7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0";
+
+/// `s`가 곡선 위수의 아래쪽 절반에 있는지 (EIP-2)
+pub fn is_low_s(s: &U256) -> bool {
+    *s <= U256::from_str_radix(SECP256K1_HALF_N, 16).unwrap_or_default()
+}
+
+/// 서명 해시와 (recovery id, r, s)로 서명한 계정의 주소를 복구한다.
+/// legacy, typed 트랜잭션 모두 이 함수로 보낸 사람을 찾는다.
+pub fn recover_sender(signing_hash: H256, recovery_id: i32, r: H256, s: H256) -> Result<Address, RecoveryError> {
+    let signature = make_signature(r, s);
+    let pubkey = web3_signer::signing::recover(signing_hash.as_bytes(), signature.as_bytes(), recovery_id)?;
+    Ok(pubkey.address())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::str::FromStr;
+    use ethereum_types::{Address, H256, U256};
+    use crate::typed_transaction::TypedTransaction;
+    use super::{MetamaskRawTransaction, RawTransaction};
+
+    const EIP155_EXAMPLE: &str = "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+    fn decode(raw: &str) -> RawTransaction {
+        let raw = hex::decode(raw).unwrap();
+        RawTransaction::try_from(rlp::decode::<MetamaskRawTransaction>(raw.as_slice()).unwrap()).unwrap()
+    }
+
+    /// EIP-155 예제의 필드 하나를 바꿔서 다시 RLP로 인코딩한다.
+    fn reencode<F: FnOnce(&mut MetamaskRawTransaction)>(f: F) -> Vec<u8> {
+        let raw = hex::decode(EIP155_EXAMPLE).unwrap();
+        let mut mrtx = rlp::decode::<MetamaskRawTransaction>(raw.as_slice()).unwrap();
+        f(&mut mrtx);
+        rlp::encode(&mrtx).to_vec()
+    }
+
+    /// EIP-155 본문의 예제 (chain id 1, 개인키 0x4646...46)
+    #[test]
+    fn eip155_example() {
+        let tx = decode(EIP155_EXAMPLE);
+        assert_eq!(tx.chain_id(), Some(1));
+        assert_eq!(tx.recovery_id(), Some(0));
+        assert_eq!(tx.signing_hash(), H256::from_str("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53").unwrap());
+        assert_eq!(tx.sender().unwrap(), Address::from_str("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f").unwrap());
+    }
+
+    /// mainnet 0x929ff27a5c7833953df23103c4eb55ebdfb698678139d751c51932163877fada
+    #[test]
+    fn eip155_mainnet_token_transfer() {
+        let tx = decode("f8aa808512ec276caf83010e2b94dac17f958d2ee523a2206206994597c13d831ec780b844a9059cbb000000000000000000000000fdae129ecc2c27d166a3131098bc05d143fa258e0000000000000000000000000000000000000000000000000000000002faf08025a0c81e70f9e49e0d3b854720143e86d172fecc9e76ef8a8666f2fdc017017c5141a01dd3410180f6a6ca3e25ad3058789cd0df3321ed76b5b4dbe0a2bb2dc28ae274");
+        assert_eq!(tx.chain_id(), Some(1));
+        assert_eq!(tx.sender().unwrap(), Address::from_str("c26ad91f4e7a0cad84c4b9315f420ca9217e315d").unwrap());
+    }
+
+    /// mainnet 0x280cde7cdefe4b188750e76c888f13bd05ce9a4d7767730feefe8a0e50ca6fc4
+    #[test]
+    fn eip155_mainnet_swap() {
+        let tx = decode("f9015482078b8505d21dba0083022ef1947a250d5630b4cf539739df2c5dacb4c659f2488d880c46549a521b13d8b8e47ff36ab50000000000000000000000000000000000000000000066ab5a608bd00a23f2fe000000000000000000000000000000000000000000000000000000000000008000000000000000000000000048c04ed5691981c42154c6167398f95e8f38a7ff00000000000000000000000000000000000000000000000000000000632ceac70000000000000000000000000000000000000000000000000000000000000002000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc20000000000000000000000006c6ee5e31d828de241282b9606c8e98ea48526e225a0c9077369501641a92ef7399ff81c21639ed4fd8fc69cb793cfa1dbfab342e10aa0615facb2f1bcf3274a354cfe384a38d0cc008a11c2dd23a69111bc6930ba27a8");
+        assert_eq!(tx.chain_id(), Some(1));
+        assert_eq!(tx.sender().unwrap(), Address::from_str("a12e1462d0ced572f396f58b6e2d03894cd7c8a4").unwrap());
+    }
+
+    /// mainnet 0x97efb58d2b42df8d68ab5899ff42b16c7e0af35ed86ae4adb8acaad7e444220c (EIP-155 이전, v = 0x1c)
+    #[test]
+    fn pre_eip155_mainnet() {
+        let tx = decode("f8708311f3988504a817c80083015f90948fbeb4488a08d60979b5aa9e13dd00b2726320b2880f606682badd7800801ca05d71a4a548503f2916d10c6b1a1557a0e7352eb041acb2bac99d1ad6bb49fd45a02627bf6d35be48b0e56c61733f63944c0ebcaa85cb4ed6bc7cba3161ba85e0e8");
+        assert_eq!(tx.chain_id(), None);
+        assert_eq!(tx.recovery_id(), Some(1));
+        assert_eq!(tx.sender().unwrap(), Address::from_str("2a65aca4d5fc5b5c859090a6c34d164135398226").unwrap());
+    }
+
+    #[test]
+    fn invalid_v() {
+        let mut tx = decode("f8708311f3988504a817c80083015f90948fbeb4488a08d60979b5aa9e13dd00b2726320b2880f606682badd7800801ca05d71a4a548503f2916d10c6b1a1557a0e7352eb041acb2bac99d1ad6bb49fd45a02627bf6d35be48b0e56c61733f63944c0ebcaa85cb4ed6bc7cba3161ba85e0e8");
+        for v in [0u64, 1, 26, 29, 34].iter() {
+            tx.v = *v;
+            assert_eq!(tx.recovery_id(), None);
+            assert!(tx.sender().is_err());
+        }
+    }
+
+    type SetField = fn(&mut MetamaskRawTransaction, Vec<u8>);
+
+    /// 길이가 맞지 않는 필드는 RLP로는 올바르더라도 디코딩에 실패한다.
+    #[test]
+    fn field_length_out_of_range() {
+        let cases: Vec<(&str, SetField, usize)> = vec![
+            ("nonce", |mrtx, v| mrtx.nonce = v, 9),
+            ("gas_price", |mrtx, v| mrtx.gas_price = v, 33),
+            ("gas", |mrtx, v| mrtx.gas = v, 33),
+            ("recipient", |mrtx, v| mrtx.recipient = v, 19),
+            ("recipient", |mrtx, v| mrtx.recipient = v, 21),
+            ("value", |mrtx, v| mrtx.value = v, 33),
+            ("v", |mrtx, v| mrtx.v = v, 9),
+            ("r", |mrtx, v| mrtx.r = v, 33),
+            ("s", |mrtx, v| mrtx.s = v, 33),
+        ];
+        for (field, set, len) in cases {
+            let raw = reencode(|mrtx| set(mrtx, vec![1; len]));
+            assert!(TypedTransaction::decode(raw.as_slice()).is_err(), "{} of {} bytes", field, len);
+        }
+    }
+
+    /// u64를 넘는 gas도 서명 해시는 계산할 수 있다.
+    #[test]
+    fn gas_over_u64() {
+        let raw = reencode(|mrtx| mrtx.gas = vec![1; 9]);
+        let tx = TypedTransaction::decode(raw.as_slice()).unwrap();
+        assert_eq!(tx.gas(), U256::from_big_endian(&[1; 9]));
+        assert_ne!(tx.signing_hash(), decode(EIP155_EXAMPLE).signing_hash());
+    }
+
+    /// 빈 정수 필드는 0이다.
+    #[test]
+    fn empty_fields() {
+        let raw = reencode(|mrtx| {
+            mrtx.nonce = vec![];
+            mrtx.v = vec![];
+        });
+        match TypedTransaction::decode(raw.as_slice()).unwrap() {
+            TypedTransaction::Legacy(tx) => {
+                assert_eq!(tx.nonce, 0);
+                assert_eq!(tx.v, 0);
+            }
+            _ => panic!("not a legacy transaction"),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use ethereum_types::{Address, Bloom, H256, U256, U64};
+use rlp::{Encodable, RlpStream};
+use serde::Serialize;
+use crate::bytes::Bytes;
+use crate::typed_transaction::LEGACY_TX_TYPE;
+
+/// 트랜잭션이 남긴 로그
+/// # [Fields]
+/// - `address: Address`                - 로그를 남긴 계정
+/// - `topics: Vec<H256>`               - 인덱스된 값 (첫 번째는 이벤트 시그니처)
+/// - `data: Bytes`                     - 인덱스되지 않은 값
+/// - `block_hash: H256`                - 로그가 들어 있는 블록
+/// - `block_number: U64`
+/// - `transaction_hash: H256`
+/// - `transaction_index: U64`          - 블록 안에서 트랜잭션의 위치
+/// - `log_index: U64`                  - 블록 안에서 로그의 위치
+/// - `removed: bool`                   - reorg로 빠진 로그. 목 노드는 reorg가 없으므로 항상 false
+#[derive(Serialize, Clone, Debug)]
+pub struct Log {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Bytes,
+    #[serde(rename="blockHash")]
+    pub block_hash: H256,
+    #[serde(rename="blockNumber")]
+    pub block_number: U64,
+    #[serde(rename="transactionHash")]
+    pub transaction_hash: H256,
+    #[serde(rename="transactionIndex")]
+    pub transaction_index: U64,
+    #[serde(rename="logIndex")]
+    pub log_index: U64,
+    pub removed: bool,
+}
+
+/// receipt trie에 들어가는 로그. `[address, topics, data]`
+impl Encodable for Log {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(3);
+        s.append(&self.address);
+        s.append_list(&self.topics);
+        s.append(&self.data.0);
+    }
+}
+
+/// Transaction Receipt
+/// eth_getTransactionReceipt의 결과이며, 블록을 만들 때 함께 만든다.
+/// EVM을 실행하지 않으므로 `status`는 항상 1이고, `gasUsed`는 intrinsic gas다.
+/// # [Fields]
+/// - `transaction_hash: H256`
+/// - `transaction_index: U64`          - 블록 안에서 트랜잭션의 위치
+/// - `block_hash: H256`
+/// - `block_number: U64`
+/// - `from: Address`
+/// - `to: Option<Address>`             - 컨트랙트를 만드는 트랜잭션이면 null
+/// - `cumulative_gas_used: U64`        - 블록 안에서 이 트랜잭션까지 쓴 gas의 합
+/// - `gas_used: U64`
+/// - `effective_gas_price: U256`       - 실제로 낸 gas 가격
+/// - `contract_address: Option<Address>` - 만들어진 컨트랙트 주소. 아니면 null
+/// - `logs: Vec<Log>`
+/// - `logs_bloom: Bloom`               - `logs`의 2048비트 bloom
+/// - `tx_type: U64`                    - EIP-2718 트랜잭션 type
+/// - `status: U64`                     - 1 성공, 0 실패
+/// - `blob_gas_used: Option<U64>`      - blob 트랜잭션만
+/// - `blob_gas_price: Option<U256>`    - blob 트랜잭션만
+#[derive(Serialize, Clone, Debug)]
+pub struct Receipt {
+    #[serde(rename="transactionHash")]
+    pub transaction_hash: H256,
+    #[serde(rename="transactionIndex")]
+    pub transaction_index: U64,
+    #[serde(rename="blockHash")]
+    pub block_hash: H256,
+    #[serde(rename="blockNumber")]
+    pub block_number: U64,
+    pub from: Address,
+    pub to: Option<Address>,
+    #[serde(rename="cumulativeGasUsed")]
+    pub cumulative_gas_used: U64,
+    #[serde(rename="gasUsed")]
+    pub gas_used: U64,
+    #[serde(rename="effectiveGasPrice")]
+    pub effective_gas_price: U256,
+    #[serde(rename="contractAddress")]
+    pub contract_address: Option<Address>,
+    pub logs: Vec<Log>,
+    #[serde(rename="logsBloom")]
+    pub logs_bloom: Bloom,
+    #[serde(rename="type")]
+    pub tx_type: U64,
+    pub status: U64,
+    #[serde(rename="blobGasUsed", skip_serializing_if = "Option::is_none")]
+    pub blob_gas_used: Option<U64>,
+    #[serde(rename="blobGasPrice", skip_serializing_if = "Option::is_none")]
+    pub blob_gas_price: Option<U256>,
+}
+
+impl Receipt {
+    /// receipt trie에 들어가는 형태. `type || rlp([status, cumulativeGasUsed, logsBloom, logs])`
+    /// legacy 트랜잭션은 type 바이트가 없다.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(4);
+        stream.append(&self.status);
+        stream.append(&self.cumulative_gas_used);
+        stream.append(&self.logs_bloom);
+        stream.append_list(&self.logs);
+        let tx_type = self.tx_type.as_u64() as u8;
+        let mut encoded = match tx_type {
+            LEGACY_TX_TYPE => vec![],
+            _ => vec![tx_type],
+        };
+        encoded.extend_from_slice(stream.as_raw());
+        encoded
+    }
+}
+
+/// 블록에 들어간 트랜잭션의 receipt를 트랜잭션 해시로 찾는다.
+/// 트랜잭션이 어느 블록의 몇 번째에 있는지도 이 색인으로 찾는다.
+#[derive(Default)]
+pub struct ReceiptStore {
+    receipts: Mutex<HashMap<H256, Receipt>>,
+}
+
+impl ReceiptStore {
+    pub fn insert(&self, receipt: Receipt) {
+        self.receipts.lock().unwrap_or_else(|e| e.into_inner()).insert(receipt.transaction_hash, receipt);
+    }
+
+    pub fn get(&self, transaction_hash: &H256) -> Option<Receipt> {
+        self.receipts.lock().unwrap_or_else(|e| e.into_inner()).get(transaction_hash).cloned()
+    }
+}
@@ -0,0 +1,128 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use crate::response::RpcError;
+
+pub const DEFAULT_RECORD_MAX_SIZE: u64 = 10 * 1024 * 1024;
+pub const DEFAULT_RECORD_MAX_FILES: usize = 5;
+
+/// cassette 한 줄. 요청 하나와 그 응답
+/// # [Fields]
+/// - `timestamp: String`           - 요청을 받은 시각 (RFC 3339, UTC)
+/// - `peer_addr: String`           - 요청을 보낸 주소
+/// - `id: Option<Value>`           - 요청 id. notification이면 없다.
+/// - `method: String`
+/// - `params: Value`
+/// - `result: Option<Value>`       - 성공했을 때의 결과
+/// - `error: Option<RpcError>`     - 실패했을 때의 에러
+/// - `latency_ms: f64`             - 메서드를 처리하는 데 걸린 시간(ms)
+#[derive(Serialize)]
+pub struct RecordedCall<'a> {
+    pub timestamp: String,
+    #[serde(rename="peerAddr")]
+    pub peer_addr: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<&'a Value>,
+    pub method: &'a str,
+    pub params: &'a Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<&'a Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<&'a RpcError>,
+    #[serde(rename="latencyMs")]
+    pub latency_ms: f64,
+}
+
+impl<'a> RecordedCall<'a> {
+    pub fn new(received_at: DateTime<Utc>, peer_addr: &'a str, id: Option<&'a Value>, method: &'a str, params: &'a Value,
+               response: &'a Result<Value, RpcError>, latency: Duration) -> Self {
+        Self {
+            timestamp: received_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+            peer_addr,
+            id,
+            method,
+            params,
+            result: response.as_ref().ok(),
+            error: response.as_ref().err(),
+            latency_ms: latency.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+struct RecorderInner {
+    file: File,
+    size: u64,
+}
+
+/// 받은 요청과 응답을 JSONL cassette 파일에 한 줄씩 덧붙인다.
+/// 파일이 `max_size`를 넘으면 `<path>.1`, `<path>.2` ... 로 밀어내고 새 파일에 쓴다. 밀려난 파일은 `max_files`개까지 남긴다.
+pub struct Recorder {
+    path: PathBuf,
+    max_size: u64,
+    max_files: usize,
+    inner: Mutex<RecorderInner>,
+}
+
+impl Recorder {
+    pub fn open(path: &str, max_size: u64, max_files: usize) -> std::io::Result<Self> {
+        let path = PathBuf::from(path);
+        let file = open_append(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_size,
+            max_files,
+            inner: Mutex::new(RecorderInner { file, size }),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    pub fn record(&self, call: &RecordedCall) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(call)?;
+        line.push(b'\n');
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if inner.size > 0 && inner.size + line.len() as u64 > self.max_size {
+            self.rotate(&mut inner)?;
+        }
+        inner.file.write_all(line.as_slice())?;
+        inner.file.flush()?;
+        inner.size += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&self, inner: &mut RecorderInner) -> std::io::Result<()> {
+        match self.max_files {
+            0 => std::fs::remove_file(&self.path)?,
+            _ => {
+                for index in (1..self.max_files).rev() {
+                    let rotated = self.rotated_path(index);
+                    if rotated.exists() {
+                        std::fs::rename(&rotated, self.rotated_path(index + 1))?;
+                    }
+                }
+                std::fs::rename(&self.path, self.rotated_path(1))?;
+            }
+        }
+        inner.file = open_append(&self.path)?;
+        inner.size = 0;
+        Ok(())
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(format!(".{}", index));
+        PathBuf::from(rotated)
+    }
+}
+
+fn open_append(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
@@ -1,421 +1,76 @@
-use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use serde::{Serialize, Deserialize};
-use serde_json::{json, Value};
+use serde_json::{Number, Value};
 use crate::consts::RPC_VERSION;
 
-pub enum RequestIdtype {
-    Uuid,
-    U64,
-}
-
-pub trait RawRequestParams {
-    fn raw_params(&self) -> Vec<Value>;
-}
-
-pub trait CommonRpcEmptyRequest {
-    fn is_uuid(&self) -> bool;
-    fn method(&self) -> &str;
-    fn str_id(&self) -> String;
-}
-
-pub trait CommonRpcStringsRequest: RawRequestParams {
-    fn is_uuid(&self) -> bool;
-    fn method(&self) -> &str;
-    fn str_id(&self) -> String;
-    fn params(&self) -> &Vec<Value>;
-}
-
-pub trait CommonRpcIntegerRequest: RawRequestParams {
-    fn is_uuid(&self) -> bool;
-    fn method(&self) -> &str;
-    fn str_id(&self) -> String;
-}
-
-pub trait CommonRpcObjectRequest: RawRequestParams {
-    fn is_uuid(&self) -> bool;
-    fn method(&self) -> &str;
-    fn str_id(&self) -> String;
-    fn params(&self) -> HashMap<String, String>;
-}
-
-/// Empty Request with u64 ID
-#[derive(Serialize, Deserialize)]
-pub struct RpcEmptyRequest {
-    pub id: u64,
-    pub jsonrpc: String,
-    pub method: String,
-    params: Vec<u8>,
-}
-
-impl RpcEmptyRequest {
-    pub fn new(id: &u64, jsonrpc: &str, method: &str) -> Self {
-        Self {
-            id: id.clone(),
-            jsonrpc: jsonrpc.to_string(),
-            method: method.to_string(),
-            params: vec![],
+/// JSON-RPC 요청 id
+/// 숫자, 문자열, null 모두 허용하며 받은 모양 그대로 직렬화하여 돌려준다.
+/// # Example
+/// * `1`, `-5`, `"c7a1f3b2-..."`, `null`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum RpcId {
+    Number(Number),
+    String(String),
+    Null,
+}
+
+impl RpcId {
+    /// 요청 객체의 `id` 값으로부터 RpcId를 만든다.
+    /// id로 쓸 수 없는 값(bool, 배열, 객체)이면 `None`을 돌려준다.
+    pub fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Number(number) => Some(RpcId::Number(number.clone())),
+            Value::String(string) => Some(RpcId::String(string.clone())),
+            Value::Null => Some(RpcId::Null),
+            _ => None,
         }
     }
 }
 
-impl CommonRpcEmptyRequest for RpcEmptyRequest {
-    fn is_uuid(&self) -> bool {
-        false
-    }
-
-    fn method(&self) -> &str {
-        self.method.as_str()
-    }
-
-    fn str_id(&self) -> String {
-        self.id.to_string()
+impl Default for RpcId {
+    fn default() -> Self {
+        RpcId::Null
     }
 }
 
-/// Empty Request with UUID
-#[derive(Serialize, Deserialize)]
-pub struct RpcEmptyRequestWithUuid {
-    pub id: String,
-    pub jsonrpc: String,
-    pub method: String,
-    params: Vec<u8>,
-}
-
-impl RpcEmptyRequestWithUuid {
-    pub fn new(id: &str, jsonrpc: &str, method: &str) -> Self {
-        Self {
-            id: id.to_string(),
-            jsonrpc: jsonrpc.to_string(),
-            method: method.to_string(),
-            params: vec![],
+impl Display for RpcId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RpcId::Number(number) => write!(f, "{}", number),
+            RpcId::String(string) => write!(f, "{}", string),
+            RpcId::Null => write!(f, "null"),
         }
     }
 }
 
-impl CommonRpcEmptyRequest for RpcEmptyRequestWithUuid {
-    fn is_uuid(&self) -> bool {
-        true
-    }
-
-    fn method(&self) -> &str {
-        self.method.as_str()
-    }
-
-    fn str_id(&self) -> String {
-        self.id.as_str().to_string()
-    }
-}
+/// 파라미터를 사용하지 않는 메서드의 파라미터 타입 (받은 값은 무시한다)
+pub type EmptyParams = Vec<Value>;
 
-/// String Array Request with u64 ID
+/// JSON-RPC Request
+/// `P`는 메서드별 파라미터 타입이다.
 #[derive(Serialize, Deserialize)]
-pub struct RpcStringsRequest {
-    pub id: u64,
+pub struct RpcRequest<P> {
+    pub id: RpcId,
     pub jsonrpc: String,
     pub method: String,
-    pub params: Vec<Value>,
+    pub params: P,
 }
 
-impl RpcStringsRequest {
-    pub fn new(id: &u64, jsonrpc: &str, method: &str, params: &Vec<Value>) -> Self {
+impl<P> RpcRequest<P> {
+    pub fn new(id: &RpcId, method: &str, params: P) -> Self {
         Self {
             id: id.clone(),
-            jsonrpc: jsonrpc.to_string(),
+            jsonrpc: RPC_VERSION.to_string(),
             method: method.to_string(),
-            params: params.to_vec(),
+            params,
         }
     }
 }
 
-impl RawRequestParams for RpcStringsRequest {
-    fn raw_params(&self) -> Vec<Value> {
-        self.params.clone()
+impl<P: Serialize> RpcRequest<P> {
+    /// 노드가 해당 RPC를 호출할 때 보낼 JSON 문자열
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
     }
 }
-
-impl CommonRpcStringsRequest for RpcStringsRequest {
-    fn is_uuid(&self) -> bool {
-        false
-    }
-
-    fn method(&self) -> &str {
-        self.method.as_str()
-    }
-
-    fn str_id(&self) -> String {
-        self.id.to_string()
-    }
-
-    fn params(&self) -> &Vec<Value> {
-        &self.params
-    }
-}
-
-/// String Array Request with UUID
-#[derive(Serialize, Deserialize)]
-pub struct RpcStringsRequestWithUuid {
-    pub id: String,
-    pub jsonrpc: String,
-    pub method: String,
-    pub params: Vec<Value>,
-}
-
-impl RpcStringsRequestWithUuid {
-    pub fn new(id: &str, jsonrpc: &str, method: &str, params: &Vec<Value>) -> Self {
-        Self {
-            id: id.to_string(),
-            jsonrpc: jsonrpc.to_string(),
-            method: method.to_string(),
-            params: params.to_vec(),
-        }
-    }
-}
-
-impl RawRequestParams for RpcStringsRequestWithUuid {
-    fn raw_params(&self) -> Vec<Value> {
-        self.params.clone()
-    }
-}
-
-impl CommonRpcStringsRequest for RpcStringsRequestWithUuid {
-    fn is_uuid(&self) -> bool {
-        true
-    }
-
-    fn method(&self) -> &str {
-        self.method.as_str()
-    }
-
-    fn str_id(&self) -> String {
-        self.id.as_str().to_string()
-    }
-
-    fn params(&self) -> &Vec<Value> {
-        &self.params
-    }
-}
-
-/// Integer Request with u64 ID
-#[derive(Serialize, Deserialize)]
-pub struct RpcIntegerRequest {
-    pub id: u64,
-    pub jsonrpc: String,
-    pub method: String,
-    pub params: Vec<Value>,
-}
-
-impl RpcIntegerRequest {
-    pub fn new(id: &u64, jsonrpc: &str, method: &str, params: &Vec<Value>) -> Self {
-        Self {
-            id: id.clone(),
-            jsonrpc: jsonrpc.to_string(),
-            method: method.to_string(),
-            params: params.to_vec(),
-        }
-    }
-}
-
-impl RawRequestParams for RpcIntegerRequest {
-    fn raw_params(&self) -> Vec<Value> {
-        self.params.to_vec()
-    }
-}
-
-impl CommonRpcIntegerRequest for RpcIntegerRequest {
-    fn is_uuid(&self) -> bool {
-        false
-    }
-
-    fn method(&self) -> &str {
-        self.method.as_str()
-    }
-
-    fn str_id(&self) -> String {
-        self.id.to_string()
-    }
-}
-
-/// Integer Request with UUID
-#[derive(Serialize, Deserialize)]
-pub struct RpcIntegerRequestWithUuid {
-    pub id: String,
-    pub jsonrpc: String,
-    pub method: String,
-    pub params: Vec<Value>,
-}
-
-impl RpcIntegerRequestWithUuid {
-    pub fn new(id: &str, jsonrpc: &str, method: &str, params: &Vec<Value>) -> Self {
-        Self {
-            id: id.to_string(),
-            jsonrpc: jsonrpc.to_string(),
-            method: method.to_string(),
-            params: params.to_vec(),
-        }
-    }
-}
-
-impl RawRequestParams for RpcIntegerRequestWithUuid {
-    fn raw_params(&self) -> Vec<Value> {
-        self.params.to_vec()
-    }
-}
-
-impl CommonRpcIntegerRequest for RpcIntegerRequestWithUuid {
-    fn is_uuid(&self) -> bool {
-        true
-    }
-
-    fn method(&self) -> &str {
-        self.method.as_str()
-    }
-
-    fn str_id(&self) -> String {
-        self.id.as_str().to_string()
-    }
-}
-
-#[derive(Serialize, Deserialize)]
-pub struct RpcObjectRequest {
-    pub id: u64,
-    pub jsonrpc: String,
-    pub method: String,
-    pub params: Vec<Value>,
-}
-
-impl RpcObjectRequest {
-    pub fn new(id: &u64, jsonrpc: &str, method: &str, params: &Vec<Value>) -> Self {
-        Self {
-            id: id.clone(),
-            jsonrpc: jsonrpc.to_string(),
-            method: method.to_string(),
-            params: params.clone(),
-        }
-    }
-}
-
-impl RawRequestParams for RpcObjectRequest {
-    fn raw_params(&self) -> Vec<Value> {
-        self.params.to_vec()
-    }
-}
-
-impl CommonRpcObjectRequest for RpcObjectRequest {
-    fn is_uuid(&self) -> bool {
-        false
-    }
-
-    fn method(&self) -> &str {
-        self.method.as_str()
-    }
-
-    fn str_id(&self) -> String {
-        self.id.to_string()
-    }
-
-    fn params(&self) -> HashMap<String, String> {
-        let mut map = HashMap::<String, String>::new();
-        let map_list = self.params.get(0).unwrap().as_object().unwrap();
-        map_list.iter().for_each(|b| {
-            map.insert(b.0.to_string(), b.1.as_str().unwrap().to_string());
-        });
-        return map;
-    }
-}
-
-#[derive(Serialize, Deserialize)]
-pub struct RpcObjectRequestWithUuid {
-    pub id: String,
-    pub jsonrpc: String,
-    pub method: String,
-    pub params: Vec<Value>,
-}
-
-impl RpcObjectRequestWithUuid {
-    pub fn new(id: &str, jsonrpc: &str, method: &str, params: &Vec<Value>) -> Self {
-        Self {
-            id: id.to_string(),
-            jsonrpc: jsonrpc.to_string(),
-            method: method.to_string(),
-            params: params.clone(),
-        }
-    }
-}
-
-impl CommonRpcObjectRequest for RpcObjectRequestWithUuid {
-    fn is_uuid(&self) -> bool {
-        true
-    }
-
-    fn method(&self) -> &str {
-        self.method.as_str()
-    }
-
-    fn str_id(&self) -> String {
-        self.id.as_str().to_string()
-    }
-
-    fn params(&self) -> HashMap<String, String> {
-        let mut map = HashMap::<String, String>::new();
-        let map_list = self.params.get(0).unwrap().as_object().unwrap();
-        map_list.iter().for_each(|b| {
-            map.insert(b.0.to_string(), b.1.as_str().unwrap().to_string());
-        });
-        return map;
-    }
-}
-
-impl RawRequestParams for RpcObjectRequestWithUuid {
-    fn raw_params(&self) -> Vec<Value> {
-        self.params.clone()
-    }
-}
-
-pub fn new_common_rpc_empty_request(rpc_id_type: RequestIdtype, opt_rpc_id: Option<&Value>, method: &str) -> Box<dyn CommonRpcEmptyRequest> {
-    let result: Box<dyn CommonRpcEmptyRequest>;
-    match rpc_id_type {
-        RequestIdtype::Uuid => {
-            let request = RpcEmptyRequestWithUuid::new(opt_rpc_id.unwrap().as_str().unwrap(), RPC_VERSION, method);
-            result = Box::new(request);
-        }
-        RequestIdtype::U64 => {
-            let id = opt_rpc_id.unwrap().as_u64().unwrap();
-            let request = RpcEmptyRequest::new(&id, RPC_VERSION, method);
-            result = Box::new(request);
-        }
-    }
-    return result;
-}
-
-pub fn new_common_rpc_strings_request(rpc_id_type: RequestIdtype, opt_rpc_id: Option<&Value>, method: &str, params: &Vec<Value>) -> Box<dyn CommonRpcStringsRequest> {
-    let result: Box<dyn CommonRpcStringsRequest>;
-    match rpc_id_type {
-        RequestIdtype::Uuid => {
-            let rpc_id = opt_rpc_id.unwrap().as_str().unwrap();
-            let request = RpcStringsRequestWithUuid::new(rpc_id, RPC_VERSION, method, params);
-            result = Box::new(request);
-        }
-        RequestIdtype::U64 => {
-            let id = opt_rpc_id.unwrap().as_u64().unwrap();
-            let request = RpcStringsRequest::new(&id, RPC_VERSION, method, params);
-            result = Box::new(request);
-        }
-    }
-    return result;
-}
-
-pub fn new_common_rpc_object_request(rpc_id_type: RequestIdtype, opt_rpc_id: Option<&Value>, method: &str, params: &Vec<Value>) -> Box<dyn CommonRpcObjectRequest> {
-    let result: Box<dyn CommonRpcObjectRequest>;
-    match rpc_id_type {
-        RequestIdtype::Uuid => {
-            let rpc_id = opt_rpc_id.unwrap().as_str().unwrap();
-            let request = RpcObjectRequestWithUuid::new(rpc_id, RPC_VERSION, method, params);
-            result = Box::new(request);
-        }
-        RequestIdtype::U64 => {
-            let id = opt_rpc_id.unwrap().as_u64().unwrap();
-            let request = RpcObjectRequest::new(&id, RPC_VERSION, method, &params);
-            result = Box::new(request);
-        }
-    }
-    return result;
-}
\ No newline at end of file
@@ -1,199 +1,143 @@
 use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
-use std::panic::resume_unwind;
-use std::str::FromStr;
-use crate::block::PseudoBlock;
+use serde_json::Value;
 use crate::consts::RPC_VERSION;
-use crate::request::CommonRpcStringsRequest;
+use crate::request::RpcId;
 
-/// String Response with u64 ID
+/// JSON-RPC Response
+/// `R`은 메서드별 결과 타입이다.
 #[derive(Serialize, Deserialize)]
-pub struct RpcStringResponse {
-    pub id: u64,
+pub struct RpcResponse<R> {
+    pub id: RpcId,
     pub jsonrpc: String,
-    pub result: String,
+    pub result: R,
 }
 
-impl RpcStringResponse {
-    pub fn new(id: &u64, result: &str) -> Self {
+impl<R> RpcResponse<R> {
+    pub fn new(id: &RpcId, result: R) -> Self {
         Self {
             id: id.clone(),
             jsonrpc: RPC_VERSION.to_string(),
-            result: result.to_string(),
+            result,
         }
     }
 }
 
-/// String Response with UUID
-#[derive(Serialize, Deserialize)]
-pub struct RpcStringResponseWithUuid {
-    pub id: String,
-    pub jsonrpc: String,
-    pub result: String,
+impl<R: Serialize> RpcResponse<R> {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
 }
 
-impl RpcStringResponseWithUuid {
-    pub fn new(id: &str, result: &str) -> Self {
+/// JSON-RPC 2.0 오류 코드
+/// # [Codes]
+/// - `-32700` Parse error            - 서버가 JSON을 해석하지 못함
+/// - `-32600` Invalid request        - JSON은 맞지만 올바른 요청 객체가 아님
+/// - `-32601` Method not found       - 존재하지 않거나 지원하지 않는 메서드
+/// - `-32602` Invalid params         - 파라미터가 잘못됨
+/// - `-32603` Internal error         - 내부 오류
+/// - `-32000` ~ `-32005`             - EIP-1474에서 정의한 서버 오류
+pub mod error_code {
+    pub const PARSE_ERROR: i64 = -32700;
+    pub const INVALID_REQUEST: i64 = -32600;
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const INVALID_PARAMS: i64 = -32602;
+    pub const INTERNAL_ERROR: i64 = -32603;
+    pub const INVALID_INPUT: i64 = -32000;
+    pub const RESOURCE_NOT_FOUND: i64 = -32001;
+    pub const RESOURCE_UNAVAILABLE: i64 = -32002;
+    pub const TRANSACTION_REJECTED: i64 = -32003;
+    pub const METHOD_NOT_SUPPORTED: i64 = -32004;
+    pub const LIMIT_EXCEEDED: i64 = -32005;
+}
+
+/// JSON-RPC 2.0 Error Object
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: &str) -> Self {
         Self {
-            id: id.to_string(),
-            jsonrpc: RPC_VERSION.to_string(),
-            result: result.to_string(),
+            code,
+            message: message.to_string(),
+            data: None,
         }
     }
-}
 
-#[derive(Serialize, Deserialize)]
-pub struct RpcBlockResponseWithUuid {
-    pub id: String,
-    pub jsonrpc: String,
-    pub result: PseudoBlock,
-}
+    pub fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
 
-impl RpcBlockResponseWithUuid {
-    pub fn new(id: &str, result: &PseudoBlock) -> Self {
-        Self {
-            id: id.to_string(),
-            jsonrpc: RPC_VERSION.to_string(),
-            result: result.clone(),
-        }
+    pub fn parse_error() -> Self {
+        Self::new(error_code::PARSE_ERROR, "Parse error")
     }
-}
 
-#[derive(Serialize, Deserialize)]
-pub struct RpcBlockResponse {
-    pub id: u64,
-    pub jsonrpc: String,
-    pub result: PseudoBlock,
-}
+    pub fn invalid_request(reason: &str) -> Self {
+        Self::new(error_code::INVALID_REQUEST, &format!("Invalid request: {}", reason))
+    }
 
-impl RpcBlockResponse {
-    pub fn new(id: &u64, result: &PseudoBlock) -> Self {
-        Self {
-            id: id.clone(),
-            jsonrpc: RPC_VERSION.to_string(),
-            result: result.clone(),
-        }
+    pub fn method_not_found(method: &str) -> Self {
+        Self::new(error_code::METHOD_NOT_FOUND, &format!("the method {} does not exist/is not available", method))
     }
-}
 
-/// Boolean Response with u64 ID
-#[derive(Serialize, Deserialize)]
-pub struct RpcBoolResponse {
-    pub id: u64,
-    pub jsonrpc: String,
-    pub result: bool,
-}
+    pub fn invalid_params(reason: &str) -> Self {
+        Self::new(error_code::INVALID_PARAMS, &format!("Invalid params: {}", reason))
+    }
 
-impl RpcBoolResponse {
-    pub fn new(id: &u64, result: bool) -> Self {
-        Self {
-            id: id.clone(),
-            jsonrpc: RPC_VERSION.to_string(),
-            result: result.clone(),
-        }
+    pub fn internal_error(reason: &str) -> Self {
+        Self::new(error_code::INTERNAL_ERROR, &format!("Internal error: {}", reason))
     }
-}
 
-/// Boolean Response with UUID
-#[derive(Serialize, Deserialize)]
-pub struct RpcBoolResponseWithUuid {
-    pub id: String,
-    pub jsonrpc: String,
-    pub result: bool,
-}
+    pub fn invalid_input(reason: &str) -> Self {
+        Self::new(error_code::INVALID_INPUT, reason)
+    }
 
-impl RpcBoolResponseWithUuid {
-    pub fn new(id: &str, result: bool) -> Self {
-        Self {
-            id: id.to_string(),
-            jsonrpc: RPC_VERSION.to_string(),
-            result: result.clone(),
-        }
+    pub fn resource_not_found(reason: &str) -> Self {
+        Self::new(error_code::RESOURCE_NOT_FOUND, reason)
     }
-}
 
-/// String Array Response with u64 ID
-#[derive(Serialize, Deserialize)]
-pub struct RpcStringArrayResponse {
-    pub id: u64,
-    pub jsonrpc: String,
-    pub result: Vec<String>,
-}
+    pub fn resource_unavailable(reason: &str) -> Self {
+        Self::new(error_code::RESOURCE_UNAVAILABLE, reason)
+    }
 
-impl RpcStringArrayResponse {
-    pub fn new(id: &u64, result: &Vec<String>) -> Self {
-        Self {
-            id: id.clone(),
-            jsonrpc: RPC_VERSION.to_string(),
-            result: result.to_vec(),
-        }
+    pub fn transaction_rejected(reason: &str) -> Self {
+        Self::new(error_code::TRANSACTION_REJECTED, reason)
     }
-}
 
-/// String Array Response with UUID
-#[derive(Serialize, Deserialize)]
-pub struct RpcStringArrayResponseWithUuid {
-    pub id: String,
-    pub jsonrpc: String,
-    pub result: Vec<String>,
-}
+    pub fn method_not_supported(reason: &str) -> Self {
+        Self::new(error_code::METHOD_NOT_SUPPORTED, reason)
+    }
 
-impl RpcStringArrayResponseWithUuid {
-    pub fn new(id: &str, result: &Vec<String>) -> Self {
-        Self {
-            id: id.to_string(),
-            jsonrpc: RPC_VERSION.to_string(),
-            result: result.to_vec(),
-        }
+    pub fn limit_exceeded(reason: &str) -> Self {
+        Self::new(error_code::LIMIT_EXCEEDED, reason)
     }
 }
 
-/// Map type Response with u64 ID
+/// Error Response
+/// 요청의 id를 알 수 없는 경우(파싱 실패 등)에는 id가 `null`이 된다.
 #[derive(Serialize, Deserialize)]
-pub struct RpcMapResponse {
-    pub id: u64,
+pub struct RpcErrorResponse {
+    pub id: RpcId,
     pub jsonrpc: String,
-    pub result: HashMap<String, String>,
+    pub error: RpcError,
 }
 
-impl RpcMapResponse {
-    pub fn new(id: &u64, result: &HashMap<String, String>) -> Self {
+impl RpcErrorResponse {
+    pub fn new(id: &RpcId, error: &RpcError) -> Self {
         Self {
             id: id.clone(),
             jsonrpc: RPC_VERSION.to_string(),
-            result: result.clone(),
+            error: error.clone(),
         }
     }
 }
 
-/// Map type Response with UUID
-#[derive(Serialize, Deserialize)]
-pub struct RpcMapResponseWithUuid {
-    pub id: String,
-    pub jsonrpc: String,
-    pub result: HashMap<String, String>,
-}
-
-impl RpcMapResponseWithUuid {
-    pub fn new(id: &str, result: &HashMap<String, String>) -> Self {
-        Self {
-            id: id.to_string(),
-            jsonrpc: RPC_VERSION.to_string(),
-            result: result.clone(),
-        }
-    }
+pub fn new_json_rpc_error_response(id: &RpcId, error: &RpcError) -> String {
+    let res = RpcErrorResponse::new(id, error);
+    serde_json::to_string(&res).unwrap()
 }
-
-pub fn new_json_rpc_string_response(request: &Box<dyn CommonRpcStringsRequest>, data: &str) -> String {
-    return match request.is_uuid() {
-        true => {
-            let res = RpcStringResponseWithUuid::new(request.str_id().as_str(), data);
-            serde_json::to_string(&res).unwrap()
-        }
-        false => {
-            let id = u64::from_str(request.str_id().as_str()).unwrap();
-            let res = RpcStringResponse::new(&id, data);
-            serde_json::to_string(&res).unwrap()
-        }
-    }
-}
\ No newline at end of file
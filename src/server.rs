@@ -0,0 +1,491 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use log::{info, warn};
+use serde_json::Value;
+use tungstenite::WebSocket;
+use tungstenite::handshake::derive_accept_key;
+use tungstenite::protocol::Role;
+use crate::config::Config;
+use crate::handler::handle_rpc_payload;
+use crate::health::{health, ready};
+use crate::methods::RpcContext;
+use crate::methods::registry::{MethodRegistry, RpcHandler};
+use crate::miner::{self, MiningMode};
+use crate::node::{Node, ReceivedRequest, ReceivedTransaction};
+use crate::request::RpcId;
+use crate::response::{new_json_rpc_error_response, RpcError};
+use crate::websocket;
+
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(10);
+const MAX_HEADER_LINES: usize = 128;
+/// 요청 본문의 최대 크기. 넘으면 본문을 읽지 않고 413으로 응답한다.
+const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+/// 요청을 다 보내지 않고 쉬고 있는 연결 때문에 `stop()`이 끝나지 않는 일이 없도록 읽기를 기다리는 최대 시간
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Rust 테스트 코드에 넣어 쓰는 목 노드
+/// 포트 0에 바인드하여 테스트마다 따로 띄우고, drop되면 멈춘다.
+/// 같은 포트에서 `Upgrade: websocket` 요청을 받으면 WebSocket으로 처리한다.
+/// # Example
+/// ```ignore
+/// let server = MockServer::builder().chain_id(1337).start().unwrap();
+/// let url = server.url(); // http://127.0.0.1:54321
+/// // ... MetaMask/ethers 등으로 url에 요청을 보낸다.
+/// assert_eq!(server.received_transactions().len(), 1);
+/// ```
+pub struct MockServer {
+    node: Arc<Node>,
+    local_addr: SocketAddr,
+    running: Arc<AtomicBool>,
+    accept_thread: Option<JoinHandle<()>>,
+    miner_thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    pub fn builder() -> MockServerBuilder {
+        MockServerBuilder::default()
+    }
+
+    /// 기본 설정으로 띄운다.
+    pub fn start() -> std::io::Result<Self> {
+        Self::builder().start()
+    }
+
+    /// `http://<address>:<port>`
+    pub fn url(&self) -> String {
+        format!("http://{}", self.local_addr)
+    }
+
+    /// `ws://<address>:<port>`
+    pub fn ws_url(&self) -> String {
+        format!("ws://{}", self.local_addr)
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    pub fn node(&self) -> &Node {
+        &self.node
+    }
+
+    /// 지금까지 받은 요청 (notification 포함, 받은 순서)
+    pub fn received_requests(&self) -> Vec<ReceivedRequest> {
+        self.node.history.requests()
+    }
+
+    /// 지금까지 받은 트랜잭션 (받은 순서)
+    pub fn received_transactions(&self) -> Vec<ReceivedTransaction> {
+        self.node.history.transactions()
+    }
+
+    /// 새 연결을 더 받지 않고, 처리 중인 요청이 끝날 때까지 기다린다.
+    pub fn stop(&mut self) {
+        self.node.lifecycle.begin_shutdown();
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(accept_thread) = self.accept_thread.take() {
+            let _ = accept_thread.join();
+            info!("[MOCK_SERVER] {} stopped", self.local_addr);
+        }
+        if let Some(miner_thread) = self.miner_thread.take() {
+            let _ = miner_thread.join();
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// MockServer 설정
+/// 바인드 주소는 기본 `127.0.0.1:0`이며, `config`에 들어 있는 주소와 포트는 쓰지 않는다.
+pub struct MockServerBuilder {
+    bind_address: String,
+    port: u16,
+    config: Config,
+    registry: MethodRegistry,
+}
+
+impl Default for MockServerBuilder {
+    fn default() -> Self {
+        Self {
+            bind_address: "127.0.0.1".to_string(),
+            port: 0,
+            config: Config::default(),
+            registry: MethodRegistry::with_default_methods(),
+        }
+    }
+}
+
+impl MockServerBuilder {
+    pub fn bind_address(mut self, bind_address: &str) -> Self {
+        self.bind_address = bind_address.to_string();
+        self
+    }
+
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn chain_id(mut self, chain_id: u64) -> Self {
+        self.config.chain_id = chain_id;
+        self
+    }
+
+    pub fn registry(mut self, registry: MethodRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    pub fn register<H: RpcHandler + 'static>(mut self, name: &str, handler: H) -> Self {
+        self.registry.register(name, handler);
+        self
+    }
+
+    pub fn register_fn<F>(mut self, name: &str, f: F) -> Self
+        where F: Fn(Value, &RpcContext) -> Result<Value, RpcError> + Send + Sync + 'static {
+        self.registry.register_fn(name, f);
+        self
+    }
+
+    /// 설정이 올바르지 않으면(`Config::validate`) `InvalidInput` 에러를 돌려준다.
+    pub fn start(self) -> std::io::Result<MockServer> {
+        self.config.validate()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+        let listener = TcpListener::bind((self.bind_address.as_str(), self.port))?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+        let node = Arc::new(Node::new(self.config, self.registry));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let accept_node = node.clone();
+        let accept_running = running.clone();
+        let accept_thread = std::thread::Builder::new()
+            .name(format!("mock-server-{}", local_addr.port()))
+            .spawn(move || accept_loop(listener, accept_node, accept_running))?;
+        info!("[MOCK_SERVER] listening on {}", local_addr);
+
+        let miner_thread = match node.config.mining {
+            MiningMode::Interval => {
+                let miner_node = node.clone();
+                Some(std::thread::Builder::new()
+                    .name(format!("mock-miner-{}", local_addr.port()))
+                    .spawn(move || miner::run_interval(&miner_node))?)
+            }
+            _ => None,
+        };
+
+        Ok(MockServer {
+            node,
+            local_addr,
+            running,
+            accept_thread: Some(accept_thread),
+            miner_thread,
+        })
+    }
+}
+
+fn accept_loop(listener: TcpListener, node: Arc<Node>, running: Arc<AtomicBool>) {
+    let mut connections: Vec<JoinHandle<()>> = vec![];
+    while running.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, peer_addr)) => {
+                let node = node.clone();
+                connections.push(std::thread::spawn(move || handle_connection(stream, peer_addr, &node)));
+            }
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => std::thread::sleep(ACCEPT_INTERVAL),
+            Err(e) => warn!("[MOCK_SERVER] accept failed: {}", e),
+        }
+        connections.retain(|connection| !connection.is_finished());
+    }
+    // 처리 중인 요청은 끝까지 응답한다.
+    for connection in connections {
+        let _ = connection.join();
+    }
+}
+
+/// 요청 하나를 읽고 응답한 뒤 연결을 닫는다. (`Connection: close`)
+fn handle_connection(stream: TcpStream, peer_addr: SocketAddr, node: &Node) {
+    let _ = stream.set_nonblocking(false);
+    if let Err(e) = stream.set_read_timeout(Some(READ_TIMEOUT)) {
+        warn!("[MOCK_SERVER] ip={} could not set the read timeout: {}", peer_addr, e);
+        return;
+    }
+    let mut reader = BufReader::new(match stream.try_clone() {
+        Ok(stream) => stream,
+        Err(_) => return,
+    });
+    let mut writer = stream;
+
+    let mut request = match read_request(&mut reader) {
+        Ok(request) => request,
+        Err(e) => {
+            warn!("[MOCK_SERVER] ip={} broken http request: {}", peer_addr, e);
+            return;
+        }
+    };
+    if request.content_length > MAX_BODY_SIZE {
+        warn!("[MOCK_SERVER] ip={} body too large: {} bytes (max {})", peer_addr, request.content_length, MAX_BODY_SIZE);
+        let _ = write_response(&mut writer, 413, &node.config.cors.response_headers(request.header("origin")), "");
+        return;
+    }
+    if let Err(e) = read_body(&mut reader, &mut request) {
+        warn!("[MOCK_SERVER] ip={} broken http request: {}", peer_addr, e);
+        return;
+    }
+    let peer_addr = peer_addr.to_string();
+    if request.header("upgrade").map_or(false, |upgrade| upgrade.eq_ignore_ascii_case("websocket")) {
+        upgrade_websocket(reader, writer, &request, &peer_addr, node);
+        return;
+    }
+    let cors = &node.config.cors;
+    let origin = request.header("origin");
+
+    let (status, mut headers, body) = match request.method.as_str() {
+        "GET" if request.path == "/health" => {
+            let (_, data) = health(node);
+            (200, vec![], data)
+        }
+        "GET" if request.path == "/ready" => match ready(node) {
+            (true, data) => (200, vec![], data),
+            (false, data) => (503, vec![], data),
+        },
+        _ if request.path != "/" => (404, cors.response_headers(origin), "".to_string()),
+        "OPTIONS" => match cors.preflight_headers(origin, request.header("access-control-request-headers")) {
+            Some(headers) => (204, headers, "".to_string()),
+            None => (403, vec![], "".to_string()),
+        },
+        "POST" => {
+            let headers = cors.response_headers(origin);
+            match request.body.len() {
+                0 => (400, headers, new_json_rpc_error_response(&RpcId::Null, &RpcError::invalid_request("empty body"))),
+                _ => match node.lifecycle.enter() {
+                    Some(_in_flight) => {
+                        let ctx = RpcContext { node, channel: None, subscriber: None };
+                        (200, headers, handle_rpc_payload(request.body.as_str(), &peer_addr, &ctx))
+                    }
+                    None => (503, headers, new_json_rpc_error_response(&RpcId::Null, &RpcError::resource_unavailable("node is shutting down"))),
+                },
+            }
+        }
+        _ => (405, cors.response_headers(origin), "".to_string()),
+    };
+    if !body.is_empty() {
+        headers.push(("Content-Type", "application/json".to_string()));
+    }
+    let _ = write_response(&mut writer, status, &headers, body.as_str());
+}
+
+/// 101로 응답하고 같은 연결을 WebSocket으로 쓴다.
+fn upgrade_websocket(reader: BufReader<TcpStream>, mut writer: TcpStream, request: &Request, peer_addr: &str, node: &Node) {
+    let key = match request.header("sec-websocket-key") {
+        Some(key) => key,
+        None => {
+            let _ = write_response(&mut writer, 400, &vec![], "");
+            return;
+        }
+    };
+    let response = format!("HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        derive_accept_key(key.as_bytes()));
+    if writer.write_all(response.as_bytes()).and_then(|_| writer.flush()).is_err() {
+        return;
+    }
+    // handshake 뒤에 이미 읽어 둔 프레임이 있으면 이어서 읽는다.
+    let socket = WebSocket::from_partially_read(writer, reader.buffer().to_vec(), Role::Server, None);
+    websocket::serve(socket, peer_addr, node);
+}
+
+struct Request {
+    method: String,
+    path: String,
+    headers: HashMap<String, String>,
+    content_length: usize,
+    body: String,
+}
+
+impl Request {
+    /// 헤더 이름은 소문자로 찾는다.
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(|value| value.as_str())
+    }
+}
+
+fn invalid(reason: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, reason.to_string())
+}
+
+/// 요청 줄과 헤더를 읽는다. 본문은 크기를 확인한 뒤 `read_body`로 읽는다.
+fn read_request<R: BufRead>(reader: &mut R) -> std::io::Result<Request> {
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().ok_or_else(|| invalid("empty request line"))?.to_uppercase();
+    let path = parts.next().unwrap_or("/").to_string();
+
+    let mut headers = HashMap::new();
+    for _ in 0..MAX_HEADER_LINES {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let content_length = match headers.get("content-length") {
+        Some(length) => length.parse::<usize>().map_err(|_| invalid("invalid content-length"))?,
+        None => 0,
+    };
+
+    Ok(Request { method, path, headers, content_length, body: String::new() })
+}
+
+fn read_body<R: BufRead>(reader: &mut R, request: &mut Request) -> std::io::Result<()> {
+    let mut body = vec![0u8; request.content_length];
+    reader.read_exact(&mut body)?;
+    request.body = String::from_utf8(body).map_err(|_| invalid("body is not utf-8"))?;
+    Ok(())
+}
+
+fn write_response<W: Write>(writer: &mut W, status: u16, headers: &Vec<(&'static str, String)>, body: &str) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    let mut response = format!("HTTP/1.1 {} {}\r\n", status, reason);
+    for (name, value) in headers.iter() {
+        response.push_str(format!("{}: {}\r\n", name, value).as_str());
+    }
+    response.push_str(format!("Content-Length: {}\r\nConnection: close\r\n\r\n", body.len()).as_str());
+    response.push_str(body);
+    writer.write_all(response.as_bytes())?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use serde_json::{json, Value};
+    use crate::config::Config;
+    use super::MockServer;
+
+    /// 본문을 POST로 보내고 (status, 응답 본문)을 돌려준다.
+    fn post(server: &MockServer, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        write!(stream, "POST / HTTP/1.1\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response.split(' ').nth(1).unwrap().parse().unwrap();
+        let body = response.split_once("\r\n\r\n").map(|(_, body)| body).unwrap_or_default().to_string();
+        (status, body)
+    }
+
+    fn post_json(server: &MockServer, body: Value) -> Value {
+        let (status, body) = post(server, body.to_string().as_str());
+        assert_eq!(status, 200);
+        serde_json::from_str(body.as_str()).unwrap()
+    }
+
+    #[test]
+    fn request() {
+        let server = MockServer::builder().chain_id(1337).start().unwrap();
+        let response = post_json(&server, json!({"jsonrpc": "2.0", "id": 1, "method": "eth_chainId", "params": []}));
+        assert_eq!(response, json!({"jsonrpc": "2.0", "id": 1, "result": "0x539"}));
+
+        let requests = server.received_requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "eth_chainId");
+    }
+
+    /// 배치의 응답은 같은 순서로 오고, notification은 응답에서 빠지지만 기록에는 남는다.
+    #[test]
+    fn batch_with_notification() {
+        let server = MockServer::builder().chain_id(1337).start().unwrap();
+        let response = post_json(&server, json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "eth_chainId"},
+            {"jsonrpc": "2.0", "method": "eth_blockNumber"},
+            {"jsonrpc": "2.0", "id": "b", "method": "no_such_method"},
+        ]));
+        let responses = response.as_array().unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["id"], json!(1));
+        assert_eq!(responses[0]["result"], json!("0x539"));
+        assert_eq!(responses[1]["id"], json!("b"));
+        assert_eq!(responses[1]["error"]["code"], json!(-32601));
+
+        let methods: Vec<String> = server.received_requests().into_iter().map(|request| request.method).collect();
+        assert_eq!(methods, vec!["eth_chainId", "eth_blockNumber", "no_such_method"]);
+    }
+
+    /// notification만 보내면 응답 본문이 없다.
+    #[test]
+    fn notification() {
+        let server = MockServer::start().unwrap();
+        let (status, body) = post(&server, r#"{"jsonrpc":"2.0","method":"eth_chainId"}"#);
+        assert_eq!(status, 200);
+        assert!(body.is_empty());
+        assert_eq!(server.received_requests().len(), 1);
+    }
+
+    /// 메서드 안의 panic은 그 요청만 실패시키고 서버는 계속 응답한다.
+    #[test]
+    fn panic_is_isolated() {
+        let server = MockServer::builder()
+            .register_fn("test_panic", |_, _| panic!("boom"))
+            .start()
+            .unwrap();
+        let response = post_json(&server, json!({"jsonrpc": "2.0", "id": 1, "method": "test_panic"}));
+        assert_eq!(response["error"]["code"], json!(-32603));
+        assert_eq!(server.node().registry.recovered_panics().get("test_panic"), Some(&1));
+
+        let response = post_json(&server, json!({"jsonrpc": "2.0", "id": 2, "method": "eth_chainId"}));
+        assert!(response.get("result").is_some());
+    }
+
+    /// drop하면 더 이상 연결을 받지 않는다.
+    #[test]
+    fn drop_stops_the_server() {
+        let server = MockServer::start().unwrap();
+        let local_addr = server.local_addr();
+        drop(server);
+        assert!(TcpStream::connect(local_addr).is_err());
+    }
+
+    #[test]
+    fn invalid_config() {
+        let config = Config { filter_timeout: 0, ..Config::default() };
+        let err = MockServer::builder().config(config).start().err().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        let config = Config { record_max_size: 0, ..Config::default() };
+        let err = MockServer::builder().config(config).start().err().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
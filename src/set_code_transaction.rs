@@ -0,0 +1,293 @@
+use crypto::hash::keccak256;
+use ethereum_types::{Address, H256, U256, U64};
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+use serde::{Deserialize, Serialize};
+use web3_signer::error::RecoveryError;
+use crate::access_list_transaction::AccessListItem;
+use crate::bytes::Bytes;
+use crate::raw_transaction::{is_low_s, recover_sender};
+use crate::typed_transaction::{h256_from_u256, with_tx_type, SET_CODE_TX_TYPE};
+
+/// authorization 서명 해시 앞에 붙는 magic
+pub const AUTHORIZATION_MAGIC: u8 = 0x05;
+/// 위임받은 계정의 코드. `0xef0100 || address`
+pub const DELEGATION_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
+/// EIP-7702 authorization tuple
+/// `[chainId, address, nonce, yParity, r, s]`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Authorization {
+    #[serde(rename="chainId")]
+    pub chain_id: U256,
+    pub address: Address,
+    pub nonce: U64,
+    #[serde(rename="yParity")]
+    pub y_parity: U64,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl Authorization {
+    /// `keccak256(0x05 || rlp([chainId, address, nonce]))`
+    pub fn signing_hash(&self) -> H256 {
+        let mut stream = RlpStream::new_list(3);
+        stream.append(&self.chain_id);
+        stream.append(&self.address);
+        stream.append(&self.nonce);
+        let mut data = vec![AUTHORIZATION_MAGIC];
+        data.extend_from_slice(stream.as_raw());
+        keccak256(data.as_slice())
+    }
+
+    /// 서명한 계정(authority)을 복구한다.
+    pub fn authority(&self) -> Result<Address, RecoveryError> {
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(h256_from_u256(&self.r).as_bytes());
+        signature[32..].copy_from_slice(h256_from_u256(&self.s).as_bytes());
+        let pubkey = web3_signer::signing::recover(self.signing_hash().as_bytes(), &signature, self.y_parity.as_u64() as i32)?;
+        Ok(pubkey.address())
+    }
+
+    /// tuple을 검사하고 authority를 복구한다.
+    /// chain id는 0(모든 체인)이거나 노드의 chain id와 같아야 한다.
+    pub fn verify(&self, chain_id: u64) -> AuthorizationReport {
+        let mut report = AuthorizationReport {
+            authority: None,
+            address: self.address,
+            chain_id: self.chain_id,
+            nonce: self.nonce,
+            valid: false,
+            reason: None,
+        };
+        let invalid = |mut report: AuthorizationReport, reason: String| {
+            report.reason = Some(reason);
+            report
+        };
+        if !self.chain_id.is_zero() && self.chain_id != U256::from(chain_id) {
+            return invalid(report, format!("chain id {} does not match {}", self.chain_id, chain_id));
+        }
+        if self.nonce == U64::MAX {
+            return invalid(report, "nonce overflow".to_string());
+        }
+        if self.y_parity > U64::one() {
+            return invalid(report, format!("invalid y parity {}", self.y_parity));
+        }
+        if !is_low_s(&self.s) {
+            return invalid(report, "s is in the upper half of the curve order".to_string());
+        }
+        match self.authority() {
+            Ok(authority) => {
+                report.authority = Some(authority);
+                report.valid = true;
+                report
+            }
+            Err(e) => invalid(report, e.to_string()),
+        }
+    }
+}
+
+impl Encodable for Authorization {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(6);
+        s.append(&self.chain_id);
+        s.append(&self.address);
+        s.append(&self.nonce);
+        s.append(&self.y_parity);
+        s.append(&self.r);
+        s.append(&self.s);
+    }
+}
+
+impl Decodable for Authorization {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 6 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            address: rlp.val_at(1)?,
+            nonce: rlp.val_at(2)?,
+            y_parity: rlp.val_at(3)?,
+            r: rlp.val_at(4)?,
+            s: rlp.val_at(5)?,
+        })
+    }
+}
+
+/// authorization tuple 하나를 검사한 결과
+/// # Example
+/// * {"authority":"0x...","address":"0x...","chainId":"0x54","nonce":"0x0","valid":true,"reason":null}
+#[derive(Serialize, Clone, Debug)]
+pub struct AuthorizationReport {
+    pub authority: Option<Address>,
+    pub address: Address,
+    #[serde(rename="chainId")]
+    pub chain_id: U256,
+    pub nonce: U64,
+    pub valid: bool,
+    pub reason: Option<String>,
+}
+
+/// EIP-7702 (type 4) 트랜잭션
+/// `0x04 || rlp([chainId, nonce, maxPriorityFeePerGas, maxFeePerGas, gasLimit, to, value, data, accessList,
+/// authorizationList, yParity, r, s])`
+/// set-code 트랜잭션은 컨트랙트를 만들 수 없으므로 `to`가 반드시 있다.
+#[derive(Serialize, Clone, Debug)]
+pub struct SetCodeTransaction {
+    #[serde(rename="chainId")]
+    pub chain_id: U64,
+    pub nonce: U64,
+    #[serde(rename="maxPriorityFeePerGas")]
+    pub max_priority_fee_per_gas: U256,
+    #[serde(rename="maxFeePerGas")]
+    pub max_fee_per_gas: U256,
+    pub gas: U64,
+    pub to: Address,
+    pub value: U256,
+    pub input: Bytes,
+    #[serde(rename="accessList")]
+    pub access_list: Vec<AccessListItem>,
+    #[serde(rename="authorizationList")]
+    pub authorization_list: Vec<Authorization>,
+    #[serde(rename="yParity")]
+    pub y_parity: U64,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl SetCodeTransaction {
+    /// 서명할 때 쓰는 해시. `keccak256(0x04 || rlp(서명을 뺀 필드))`
+    pub fn signing_hash(&self) -> H256 {
+        let mut stream = RlpStream::new();
+        stream.begin_list(10);
+        self.rlp_append_fields(&mut stream);
+        keccak256(with_tx_type(SET_CODE_TX_TYPE, stream.as_raw()).as_slice())
+    }
+
+    pub fn sender(&self) -> Result<Address, RecoveryError> {
+        recover_sender(self.signing_hash(), self.y_parity.as_u64() as i32, h256_from_u256(&self.r), h256_from_u256(&self.s))
+    }
+
+    /// authorization tuple을 모두 검사한다. (목록 순서)
+    pub fn verify_authorizations(&self, chain_id: u64) -> Vec<AuthorizationReport> {
+        self.authorization_list.iter().map(|authorization| authorization.verify(chain_id)).collect()
+    }
+
+    fn rlp_append_fields(&self, s: &mut RlpStream) {
+        s.append(&self.chain_id);
+        s.append(&self.nonce);
+        s.append(&self.max_priority_fee_per_gas);
+        s.append(&self.max_fee_per_gas);
+        s.append(&self.gas);
+        s.append(&self.to);
+        s.append(&self.value);
+        s.append(&self.input.0);
+        s.append_list(&self.access_list);
+        s.append_list(&self.authorization_list);
+    }
+}
+
+/// 위임받은 주소가 0이면 위임을 지운다. (빈 코드)
+pub fn delegation_code(address: &Address) -> Bytes {
+    if address.is_zero() {
+        return Bytes::default();
+    }
+    let mut code = DELEGATION_PREFIX.to_vec();
+    code.extend_from_slice(address.as_bytes());
+    Bytes(code)
+}
+
+/// `0xef0100 || address` 형태의 위임 코드인지
+pub fn is_delegation(code: &Bytes) -> bool {
+    code.0.len() == DELEGATION_PREFIX.len() + 20 && code.0.starts_with(&DELEGATION_PREFIX)
+}
+
+/// type 바이트를 뺀 payload
+impl Encodable for SetCodeTransaction {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(13);
+        self.rlp_append_fields(s);
+        s.append(&self.y_parity);
+        s.append(&self.r);
+        s.append(&self.s);
+    }
+}
+
+impl Decodable for SetCodeTransaction {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 13 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            nonce: rlp.val_at(1)?,
+            max_priority_fee_per_gas: rlp.val_at(2)?,
+            max_fee_per_gas: rlp.val_at(3)?,
+            gas: rlp.val_at(4)?,
+            to: rlp.val_at(5)?,
+            value: rlp.val_at(6)?,
+            input: Bytes(rlp.val_at(7)?),
+            access_list: rlp.list_at(8)?,
+            authorization_list: rlp.list_at(9)?,
+            y_parity: rlp.val_at(10)?,
+            r: rlp.val_at(11)?,
+            s: rlp.val_at(12)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use ethereum_types::{Address, H256};
+    use crate::bytes::Bytes;
+    use crate::typed_transaction::TypedTransaction;
+    use super::{delegation_code, is_delegation, SetCodeTransaction};
+
+    /// chain id 1, 개인키 0x00...01로 서명
+    /// authorization 1개: chain id 1, address 0x00...c0de, nonce 0. 개인키 0x4646...46으로 서명
+    const RAW: &str = "04f8ca0180843b9aca008506fc23ac00830186a09435353535353535353535353535353535353535358080c0f85cf85a0194000000000000000000000000000000000000c0de8080a09eae4cd5a4eb0f1bb3e41098ce93e884c347d4bb01647ce088809ba512ed5109a04109fbbb6bcaba68b3e9e95cfa9038ad6843006ffaf2acec42d1b3c5e615e22901a0663aa6c96789f1bc7b5d644f6f34b5e260fad26e5b9d17a57dac43d4fa12128ba05bb8c29c7b16a19cc1ddd576b9998dda36208182d581994513d38a6bef2eeec0";
+
+    fn decode() -> SetCodeTransaction {
+        match TypedTransaction::decode(hex::decode(RAW).unwrap().as_slice()).unwrap() {
+            TypedTransaction::SetCode(tx) => tx,
+            _ => panic!("not a set code transaction"),
+        }
+    }
+
+    #[test]
+    fn signed_set_code_transaction() {
+        let raw = hex::decode(RAW).unwrap();
+        let tx = TypedTransaction::decode(raw.as_slice()).unwrap();
+        assert_eq!(tx.signing_hash(), H256::from_str("f9be1c2d113863e198a614379b86c2ccdff5c730e41649e120a13fd06d60a37e").unwrap());
+        assert_eq!(tx.hash(raw.as_slice()), H256::from_str("f31f488ec086fa96fd249fd6e4be2d1287bbdb03a421d2db0e9bb5992a1024c0").unwrap());
+        assert_eq!(tx.sender().unwrap(), Address::from_str("7e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap());
+        assert_eq!(tx.encode(), raw);
+    }
+
+    #[test]
+    fn authorization_authority() {
+        let tx = decode();
+        let authorization = &tx.authorization_list[0];
+        assert_eq!(authorization.signing_hash(), H256::from_str("ef1b8d3feeb6ac8f9d509a9e90d9f2d0dc255230d2e8f1a1949ad2a94f93d7bd").unwrap());
+        assert_eq!(authorization.authority().unwrap(), Address::from_str("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f").unwrap());
+
+        let reports = tx.verify_authorizations(1);
+        assert!(reports[0].valid);
+        assert_eq!(reports[0].authority, Some(Address::from_str("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f").unwrap()));
+        let reports = tx.verify_authorizations(5);
+        assert!(!reports[0].valid);
+        assert_eq!(reports[0].authority, None);
+    }
+
+    #[test]
+    fn delegation() {
+        let address = Address::from_low_u64_be(0xc0de);
+        let code = delegation_code(&address);
+        assert_eq!(code.0, hex::decode("ef0100000000000000000000000000000000000000c0de").unwrap());
+        assert!(is_delegation(&code));
+        assert_eq!(delegation_code(&Address::zero()).0, Vec::<u8>::new());
+        assert!(!is_delegation(&Bytes(vec![0xef, 0x01, 0x00])));
+        assert!(!is_delegation(&Bytes(vec![0x60, 0x00])));
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use ethereum_types::{Address, H256, U64};
+use serde::Serialize;
+use crate::raw_transaction::is_low_s;
+use crate::set_code_transaction::AuthorizationReport;
+use crate::typed_transaction::TypedTransaction;
+
+/// 받은 트랜잭션의 서명을 검사한 결과
+/// 문제가 하나라도 있으면 `valid`가 false이고, 이유가 `reasons`에 들어간다.
+/// # Example
+/// * {"hash":"0x...","type":"0x2","signingHash":"0x...","sender":"0x...","chainId":84,"expectedChainId":84,
+///    "chainIdMatches":true,"lowS":true,"valid":true,"reasons":[],"transaction":{...}}
+#[derive(Serialize, Clone)]
+pub struct SignatureReport {
+    pub hash: H256,
+    #[serde(rename="type")]
+    pub tx_type: U64,
+    #[serde(rename="signingHash")]
+    pub signing_hash: H256,
+    pub sender: Option<Address>,
+    /// 서명에 들어 있는 chain id. EIP-155 이전 legacy 서명에는 없다.
+    #[serde(rename="chainId")]
+    pub chain_id: Option<u64>,
+    #[serde(rename="expectedChainId")]
+    pub expected_chain_id: u64,
+    /// chain id가 없으면 null
+    #[serde(rename="chainIdMatches")]
+    pub chain_id_matches: Option<bool>,
+    #[serde(rename="lowS")]
+    pub low_s: bool,
+    /// type 4 트랜잭션의 authorization tuple 검사 결과
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub authorizations: Option<Vec<AuthorizationReport>>,
+    pub valid: bool,
+    pub reasons: Vec<String>,
+    pub transaction: TypedTransaction,
+}
+
+impl SignatureReport {
+    pub fn new(hash: H256, tx: &TypedTransaction, expected_chain_id: u64) -> Self {
+        let mut reasons = vec![];
+        let sender = match tx.sender() {
+            Ok(sender) => Some(sender),
+            Err(e) => {
+                reasons.push(format!("could not recover the sender: {}", e));
+                None
+            }
+        };
+        let chain_id = tx.chain_id();
+        let chain_id_matches = chain_id.map(|chain_id| chain_id == expected_chain_id);
+        if chain_id_matches == Some(false) {
+            reasons.push(format!("chain id {} in the signature does not match the configured chain id {}", chain_id.unwrap_or_default(), expected_chain_id));
+        }
+        let low_s = is_low_s(&tx.signature_s());
+        if !low_s {
+            reasons.push("s is in the upper half of the curve order (EIP-2)".to_string());
+        }
+        if let Err(reason) = tx.validate() {
+            reasons.push(reason);
+        }
+        let authorizations = match tx {
+            TypedTransaction::SetCode(stx) => Some(stx.verify_authorizations(expected_chain_id)),
+            _ => None,
+        };
+
+        Self {
+            hash,
+            tx_type: U64::from(tx.tx_type()),
+            signing_hash: tx.signing_hash(),
+            sender,
+            chain_id,
+            expected_chain_id,
+            chain_id_matches,
+            low_s,
+            authorizations,
+            valid: reasons.is_empty(),
+            reasons,
+            transaction: tx.clone(),
+        }
+    }
+}
+
+/// 트랜잭션 해시별 서명 검사 결과
+#[derive(Default)]
+pub struct SignatureReports {
+    reports: Mutex<HashMap<H256, SignatureReport>>,
+}
+
+impl SignatureReports {
+    pub fn insert(&self, report: SignatureReport) {
+        self.reports.lock().unwrap_or_else(|e| e.into_inner()).insert(report.hash, report);
+    }
+
+    pub fn get(&self, hash: &H256) -> Option<SignatureReport> {
+        self.reports.lock().unwrap_or_else(|e| e.into_inner()).get(hash).cloned()
+    }
+}
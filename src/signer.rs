@@ -2,18 +2,19 @@ use crypto::hash::keccak256;
 use ethereum_types::{Address, H256, U256};
 use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
 use crate::raw_transaction::RawTransaction;
+use crate::typed_transaction::{append_to, decode_to};
 
 pub struct Eip155Signer {
-    chain_id: U256
+    chain_id: u64
 }
 
 impl Eip155Signer {
-    pub fn new() -> Self {
-        Self { chain_id: U256::from(9u8) }
+    pub fn new(chain_id: u64) -> Self {
+        Self { chain_id }
     }
 
-    pub fn sign(nonce: u64, gas_price: U256, gas: u64, to: Address, value: U256, data: Vec<u8>) -> H256 {
-        let eip155_tx = Eip155Tx::new(nonce, gas_price.clone(), gas, to.clone(), value.clone(), data.to_vec(), 84);
+    pub fn sign(&self, nonce: u64, gas_price: U256, gas: u64, to: Option<Address>, value: U256, data: Vec<u8>) -> H256 {
+        let eip155_tx = Eip155Tx::new(nonce, gas_price.clone(), gas.into(), to.clone(), value.clone(), data.to_vec(), self.chain_id);
         let rlp_eip155_tx = rlp::encode(&eip155_tx);
         let h_tx = keccak256(rlp_eip155_tx.as_ref());
         return h_tx;
@@ -26,68 +27,76 @@ impl Eip155Signer {
     }
 }
 
+/// 서명할 때 RLP로 인코딩하는 legacy 트랜잭션
+/// - EIP-155: `rlp([nonce, gasPrice, gas, to, value, data, chainId, 0, 0])`
+/// - EIP-155 이전: `rlp([nonce, gasPrice, gas, to, value, data])` (`chain_id`가 `None`)
 pub struct Eip155Tx {
     nonce: u64,
     gas_price: U256,
-    gas: u64,
-    to: Address,
+    gas: U256,
+    to: Option<Address>,
     value: U256,
     data: Vec<u8>,
 
-    chain_id: u64,
-    a: usize,
-    b: usize,
+    chain_id: Option<u64>,
 }
 
 impl Eip155Tx {
-    pub fn new(nonce: u64, gas_price: U256, gas: u64, to: Address, value: U256, data: Vec<u8>, chain_id: u64) -> Self {
-        Self { nonce, gas_price, gas, to, value, data, chain_id, a: 0, b: 0 }
+    pub fn new(nonce: u64, gas_price: U256, gas: U256, to: Option<Address>, value: U256, data: Vec<u8>, chain_id: u64) -> Self {
+        Self { nonce, gas_price, gas, to, value, data, chain_id: Some(chain_id) }
     }
 }
 
-impl From<RawTransaction> for Eip155Tx {
-    fn from(rtx: RawTransaction) -> Self {
+impl Eip155Tx {
+    pub fn from_raw_transaction(rtx: RawTransaction, chain_id: Option<u64>) -> Self {
         Self {
             nonce: rtx.nonce.clone(),
             gas_price: rtx.gas_price.clone(),
-            gas: rtx.gas.as_u64(),
-            to: rtx.recipient.clone(),
+            gas: rtx.gas.clone(),
+            to: rtx.to.clone(),
             value: rtx.value.clone(),
             data: rtx.data.to_vec(),
 
-            chain_id: 84,
-            a: 0,
-            b: 0,
+            chain_id,
         }
     }
 }
 
 impl Encodable for Eip155Tx {
     fn rlp_append(&self, s: &mut RlpStream) {
-        s.begin_list(6);
+        match self.chain_id {
+            Some(_) => s.begin_list(9),
+            None => s.begin_list(6),
+        };
         s.append(&self.nonce);
         s.append(&self.gas_price);
         s.append(&self.gas);
-        s.append(&self.to);
+        append_to(s, &self.to);
         s.append(&self.value);
         s.append(&self.data);
-        s.append(&self.a);
-        s.append(&self.b);
+        if let Some(chain_id) = self.chain_id {
+            s.append(&chain_id);
+            s.append(&0u8);
+            s.append(&0u8);
+        }
     }
 }
 
 impl Decodable for Eip155Tx {
     fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        let chain_id = match rlp.item_count()? {
+            6 => None,
+            9 => Some(rlp.val_at(6)?),
+            _ => return Err(DecoderError::RlpIncorrectListLen),
+        };
         Ok(Self {
-            nonce: rlp.val_at(0).unwrap(),
-            gas_price: rlp.val_at(1).unwrap(),
-            gas: rlp.val_at(2).unwrap(),
-            to: rlp.val_at(3).unwrap(),
-            value: rlp.val_at(4).unwrap(),
-            data: rlp.val_at(5).unwrap(),
-            chain_id: 0,
-            a: rlp.val_at(6).unwrap(),
-            b: rlp.val_at(7).unwrap(),
+            nonce: rlp.val_at(0)?,
+            gas_price: rlp.val_at(1)?,
+            gas: rlp.val_at(2)?,
+            to: decode_to(&rlp.at(3)?)?,
+            value: rlp.val_at(4)?,
+            data: rlp.val_at(5)?,
+            chain_id,
         })
     }
-}
\ No newline at end of file
+}
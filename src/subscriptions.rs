@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use ethereum_types::{H256, U256};
+use serde::Serialize;
+use serde_json::Value;
+use crate::block::Block;
+use crate::logs::LogFilter;
+use crate::receipt::Log;
+
+/// 구독 종류
+/// - `NewHeads`                - 새 블록 헤더
+/// - `Logs`                    - 새 블록에서 필터와 맞는 로그. 블록 범위는 보지 않는다.
+/// - `NewPendingTransactions`  - 새로 받은 트랜잭션 해시
+pub enum SubscriptionKind {
+    NewHeads,
+    Logs(LogFilter),
+    NewPendingTransactions,
+}
+
+/// WebSocket 연결 하나
+/// 알림은 `sender`로 보내고, 연결을 맡은 스레드가 받아서 소켓에 쓴다.
+#[derive(Clone)]
+pub struct Subscriber {
+    pub connection_id: u64,
+    pub sender: Sender<String>,
+}
+
+struct Subscription {
+    kind: SubscriptionKind,
+    subscriber: Subscriber,
+}
+
+/// `eth_subscription` 알림
+#[derive(Serialize)]
+struct Notification<'a> {
+    jsonrpc: &'static str,
+    method: &'static str,
+    params: NotificationParams<'a>,
+}
+
+#[derive(Serialize)]
+struct NotificationParams<'a> {
+    subscription: U256,
+    result: &'a Value,
+}
+
+struct SubscriptionInner {
+    subscriptions: HashMap<U256, Subscription>,
+    next_id: U256,
+    next_connection_id: u64,
+}
+
+impl Default for SubscriptionInner {
+    fn default() -> Self {
+        Self {
+            subscriptions: HashMap::new(),
+            next_id: U256::one(),
+            next_connection_id: 1,
+        }
+    }
+}
+
+/// eth_subscribe로 만든 구독을 id로 보관하고, 블록과 트랜잭션이 생기면 알림을 보낸다.
+/// 연결이 끊기면 그 연결의 구독을 모두 지운다.
+#[derive(Default)]
+pub struct SubscriptionManager {
+    inner: Mutex<SubscriptionInner>,
+}
+
+impl SubscriptionManager {
+    /// 새 연결을 등록한다. 알림은 돌려준 `Receiver`로 받는다.
+    pub fn connect(&self) -> (Subscriber, Receiver<String>) {
+        let mut inner = self.lock();
+        let connection_id = inner.next_connection_id;
+        inner.next_connection_id += 1;
+        let (sender, receiver) = channel();
+        (Subscriber { connection_id, sender }, receiver)
+    }
+
+    /// 연결의 구독을 모두 지우고, 지운 개수를 돌려준다.
+    pub fn disconnect(&self, connection_id: u64) -> usize {
+        let mut inner = self.lock();
+        let before = inner.subscriptions.len();
+        inner.subscriptions.retain(|_, subscription| subscription.subscriber.connection_id != connection_id);
+        before - inner.subscriptions.len()
+    }
+
+    pub fn subscribe(&self, kind: SubscriptionKind, subscriber: &Subscriber) -> U256 {
+        let mut inner = self.lock();
+        let id = inner.next_id;
+        inner.next_id = id + 1;
+        inner.subscriptions.insert(id, Subscription { kind, subscriber: subscriber.clone() });
+        id
+    }
+
+    /// 같은 연결에서 만든 구독만 지울 수 있다. 지웠으면 `true`
+    pub fn unsubscribe(&self, id: &U256, connection_id: u64) -> bool {
+        let mut inner = self.lock();
+        match inner.subscriptions.get(id) {
+            Some(subscription) if subscription.subscriber.connection_id == connection_id => inner.subscriptions.remove(id).is_some(),
+            _ => false,
+        }
+    }
+
+    /// 새 블록과 그 블록의 로그를 알린다.
+    pub fn notify_block(&self, block: &Block, logs: &[Log]) {
+        let header = serde_json::to_value(block).unwrap_or_default();
+        let logs: Vec<(&Log, Value)> = logs.iter()
+            .map(|log| (log, serde_json::to_value(log).unwrap_or_default()))
+            .collect();
+        self.notify(|kind| match kind {
+            SubscriptionKind::NewHeads => vec![&header],
+            SubscriptionKind::Logs(filter) => logs.iter()
+                .filter(|(log, _)| filter.matches(log))
+                .map(|(_, value)| value)
+                .collect(),
+            SubscriptionKind::NewPendingTransactions => vec![],
+        });
+    }
+
+    pub fn notify_pending_transaction(&self, hash: H256) {
+        let hash = serde_json::to_value(hash).unwrap_or_default();
+        self.notify(|kind| match kind {
+            SubscriptionKind::NewPendingTransactions => vec![&hash],
+            _ => vec![],
+        });
+    }
+
+    /// 구독마다 `results`가 돌려준 값을 하나씩 알림으로 보낸다. 받을 연결이 없어진 구독은 지운다.
+    fn notify<'a, F>(&self, results: F)
+        where F: Fn(&SubscriptionKind) -> Vec<&'a Value> {
+        self.lock().subscriptions.retain(|id, subscription| {
+            results(&subscription.kind).into_iter().all(|result| {
+                let notification = Notification {
+                    jsonrpc: "2.0",
+                    method: "eth_subscription",
+                    params: NotificationParams { subscription: *id, result },
+                };
+                let message = serde_json::to_string(&notification).unwrap_or_default();
+                subscription.subscriber.sender.send(message).is_ok()
+            })
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SubscriptionInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -1,45 +1,168 @@
-use ethereum_types::{Address, H256, U256};
+use ethereum_types::{Address, H256, U256, U64};
 use serde::{Serialize, Deserialize};
+use crate::access_list_transaction::AccessListItem;
+use crate::bytes::Bytes;
+use crate::set_code_transaction::Authorization;
+use crate::typed_transaction::TypedTransaction;
 
+/// eth_getTransactionByHash 등이 돌려주는 트랜잭션
+/// 블록에 들어가지 않은(pending) 트랜잭션은 `blockHash`, `blockNumber`, `transactionIndex`가 null이다.
+/// `maxFeePerGas`, `accessList` 등은 해당 type의 트랜잭션에만 있다.
 #[derive(Serialize, Deserialize)]
 pub struct Transaction {
     #[serde(rename="blockHash")]
-    pub block_hash: H256,
+    pub block_hash: Option<H256>,
     #[serde(rename="blockNumber")]
-    pub block_number: u64,
+    pub block_number: Option<U64>,
     pub from: Address,
     pub gas: U256,
     #[serde(rename="gasPrice")]
     pub gas_price: U256,
+    #[serde(rename="maxFeePerGas", skip_serializing_if="Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde(rename="maxPriorityFeePerGas", skip_serializing_if="Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
     pub hash: H256,
-    pub input: Vec<u8>,
-    pub nonce: u64,
-    pub to: Address,
+    pub input: Bytes,
+    pub nonce: U64,
+    /// 컨트랙트를 만드는 트랜잭션이면 null
+    pub to: Option<Address>,
     #[serde(rename="transactionIndex")]
-    pub transaction_index: u64,
+    pub transaction_index: Option<U64>,
     pub value: U256,
-    pub v: i32,
-    pub r: H256,
-    pub s: H256,
+    #[serde(rename="type")]
+    pub tx_type: U64,
+    #[serde(rename="accessList", skip_serializing_if="Option::is_none")]
+    pub access_list: Option<Vec<AccessListItem>>,
+    #[serde(rename="chainId", skip_serializing_if="Option::is_none")]
+    pub chain_id: Option<U64>,
+    pub v: U64,
+    pub r: U256,
+    pub s: U256,
+    #[serde(rename="yParity", skip_serializing_if="Option::is_none")]
+    pub y_parity: Option<U64>,
+    #[serde(rename="maxFeePerBlobGas", skip_serializing_if="Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
+    #[serde(rename="blobVersionedHashes", skip_serializing_if="Option::is_none")]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
+    #[serde(rename="authorizationList", skip_serializing_if="Option::is_none")]
+    pub authorization_list: Option<Vec<Authorization>>,
+}
+
+impl Transaction {
+    /// 받은 트랜잭션을 JSON-RPC 응답 형태로 바꾼다. (pending)
+    pub fn new(tx: &TypedTransaction, hash: H256, from: Address) -> Self {
+        let mut rpc_tx = Self {
+            hash,
+            from,
+            tx_type: U64::from(tx.tx_type()),
+            chain_id: tx.chain_id().map(U64::from),
+            ..Self::default()
+        };
+        match tx {
+            TypedTransaction::Legacy(tx) => {
+                rpc_tx.gas = tx.gas;
+                rpc_tx.gas_price = tx.gas_price;
+                rpc_tx.input = Bytes(tx.data.to_vec());
+                rpc_tx.nonce = U64::from(tx.nonce);
+                rpc_tx.to = tx.to;
+                rpc_tx.value = tx.value;
+                rpc_tx.v = U64::from(tx.v);
+                rpc_tx.r = U256::from_big_endian(tx.r.as_slice());
+                rpc_tx.s = U256::from_big_endian(tx.s.as_slice());
+            }
+            TypedTransaction::AccessList(tx) => {
+                rpc_tx.gas = U256::from(tx.gas.as_u64());
+                rpc_tx.gas_price = tx.gas_price;
+                rpc_tx.input = tx.input.clone();
+                rpc_tx.nonce = tx.nonce;
+                rpc_tx.to = tx.to;
+                rpc_tx.value = tx.value;
+                rpc_tx.access_list = Some(tx.access_list.clone());
+                rpc_tx.v = tx.y_parity;
+                rpc_tx.r = tx.r;
+                rpc_tx.s = tx.s;
+                rpc_tx.y_parity = Some(tx.y_parity);
+            }
+            TypedTransaction::DynamicFee(tx) => {
+                rpc_tx.gas = U256::from(tx.gas.as_u64());
+                rpc_tx.gas_price = tx.max_fee_per_gas;
+                rpc_tx.max_fee_per_gas = Some(tx.max_fee_per_gas);
+                rpc_tx.max_priority_fee_per_gas = Some(tx.max_priority_fee_per_gas);
+                rpc_tx.input = tx.input.clone();
+                rpc_tx.nonce = tx.nonce;
+                rpc_tx.to = tx.to;
+                rpc_tx.value = tx.value;
+                rpc_tx.access_list = Some(tx.access_list.clone());
+                rpc_tx.v = tx.y_parity;
+                rpc_tx.r = tx.r;
+                rpc_tx.s = tx.s;
+                rpc_tx.y_parity = Some(tx.y_parity);
+            }
+            TypedTransaction::Blob(tx) => {
+                rpc_tx.gas = U256::from(tx.gas.as_u64());
+                rpc_tx.gas_price = tx.max_fee_per_gas;
+                rpc_tx.max_fee_per_gas = Some(tx.max_fee_per_gas);
+                rpc_tx.max_priority_fee_per_gas = Some(tx.max_priority_fee_per_gas);
+                rpc_tx.input = tx.input.clone();
+                rpc_tx.nonce = tx.nonce;
+                rpc_tx.to = Some(tx.to);
+                rpc_tx.value = tx.value;
+                rpc_tx.access_list = Some(tx.access_list.clone());
+                rpc_tx.max_fee_per_blob_gas = Some(tx.max_fee_per_blob_gas);
+                rpc_tx.blob_versioned_hashes = Some(tx.blob_versioned_hashes.clone());
+                rpc_tx.v = tx.y_parity;
+                rpc_tx.r = tx.r;
+                rpc_tx.s = tx.s;
+                rpc_tx.y_parity = Some(tx.y_parity);
+            }
+            TypedTransaction::SetCode(tx) => {
+                rpc_tx.gas = U256::from(tx.gas.as_u64());
+                rpc_tx.gas_price = tx.max_fee_per_gas;
+                rpc_tx.max_fee_per_gas = Some(tx.max_fee_per_gas);
+                rpc_tx.max_priority_fee_per_gas = Some(tx.max_priority_fee_per_gas);
+                rpc_tx.input = tx.input.clone();
+                rpc_tx.nonce = tx.nonce;
+                rpc_tx.to = Some(tx.to);
+                rpc_tx.value = tx.value;
+                rpc_tx.access_list = Some(tx.access_list.clone());
+                rpc_tx.authorization_list = Some(tx.authorization_list.clone());
+                rpc_tx.v = tx.y_parity;
+                rpc_tx.r = tx.r;
+                rpc_tx.s = tx.s;
+                rpc_tx.y_parity = Some(tx.y_parity);
+            }
+        }
+        rpc_tx
+    }
 }
 
 impl Default for Transaction {
     fn default() -> Self {
         Self {
-            block_hash: H256::zero(),
-            block_number: 0,
+            block_hash: None,
+            block_number: None,
             from: Address::zero(),
             gas: U256::zero(),
             gas_price: U256::zero(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
             hash: H256::zero(),
-            input: vec![],
-            nonce: 0,
-            to: Address::zero(),
-            transaction_index: 0,
+            input: Bytes::default(),
+            nonce: U64::zero(),
+            to: None,
+            transaction_index: None,
             value: U256::zero(),
-            v: 0,
-            r: H256::zero(),
-            s: H256::zero(),
+            tx_type: U64::zero(),
+            access_list: None,
+            chain_id: None,
+            v: U64::zero(),
+            r: U256::zero(),
+            s: U256::zero(),
+            y_parity: None,
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            authorization_list: None,
         }
     }
 }
@@ -52,15 +175,24 @@ impl Clone for Transaction {
             from: self.from.clone(),
             gas: self.gas.clone(),
             gas_price: self.gas_price.clone(),
+            max_fee_per_gas: self.max_fee_per_gas.clone(),
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas.clone(),
             hash: self.hash.clone(),
-            input: self.input.to_vec(),
+            input: self.input.clone(),
             nonce: self.nonce.clone(),
             to: self.to.clone(),
             transaction_index: self.transaction_index.clone(),
             value: self.value.clone(),
+            tx_type: self.tx_type.clone(),
+            access_list: self.access_list.clone(),
+            chain_id: self.chain_id.clone(),
             v: self.v.clone(),
             r: self.r.clone(),
             s: self.s.clone(),
+            y_parity: self.y_parity.clone(),
+            max_fee_per_blob_gas: self.max_fee_per_blob_gas.clone(),
+            blob_versioned_hashes: self.blob_versioned_hashes.clone(),
+            authorization_list: self.authorization_list.clone(),
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use ethereum_types::{Address, H256};
+use crate::block::{BlockTransactions, SealedBlock};
+use crate::receipt::ReceiptStore;
+use crate::transaction::Transaction;
+use crate::typed_transaction::TypedTransaction;
+
+/// 해석한 트랜잭션과 보낸 사람
+#[derive(Clone)]
+pub struct PooledTransaction {
+    pub hash: H256,
+    pub from: Address,
+    pub tx: TypedTransaction,
+}
+
+impl PooledTransaction {
+    pub fn to_rpc_transaction(&self) -> Transaction {
+        Transaction::new(&self.tx, self.hash, self.from)
+    }
+}
+
+/// 받은 트랜잭션을 해시로 찾을 수 있도록 보관한다.
+#[derive(Default)]
+pub struct TransactionPool {
+    transactions: Mutex<HashMap<H256, PooledTransaction>>,
+}
+
+impl TransactionPool {
+    pub fn insert(&self, tx: PooledTransaction) {
+        self.transactions.lock().unwrap_or_else(|e| e.into_inner()).insert(tx.hash, tx);
+    }
+
+    pub fn get(&self, hash: &H256) -> Option<PooledTransaction> {
+        self.transactions.lock().unwrap_or_else(|e| e.into_inner()).get(hash).cloned()
+    }
+
+    /// RPC 트랜잭션 객체. 블록에 들어갔으면 receipt 색인으로 블록 정보와 실제로 낸 gas 가격을 채운다.
+    pub fn rpc_transaction(&self, hash: &H256, receipts: &ReceiptStore) -> Option<Transaction> {
+        let mut rpc_tx = self.get(hash)?.to_rpc_transaction();
+        if let Some(receipt) = receipts.get(hash) {
+            rpc_tx.block_hash = Some(receipt.block_hash);
+            rpc_tx.block_number = Some(receipt.block_number);
+            rpc_tx.transaction_index = Some(receipt.transaction_index);
+            rpc_tx.gas_price = receipt.effective_gas_price;
+        }
+        Some(rpc_tx)
+    }
+
+    /// 블록에 든 트랜잭션. `full`이면 트랜잭션 객체, 아니면 해시만 돌려준다.
+    pub fn block_transactions(&self, block: &SealedBlock, full: bool, receipts: &ReceiptStore) -> BlockTransactions {
+        match full {
+            true => BlockTransactions::Full(block.transactions.iter()
+                .filter_map(|hash| self.rpc_transaction(hash, receipts))
+                .collect()),
+            false => BlockTransactions::Hashes(block.transactions.clone()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.transactions.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
@@ -0,0 +1,44 @@
+use std::hash::Hasher;
+use crypto::hash::keccak256;
+use ethereum_types::H256;
+
+/// Merkle Patricia Trie root 계산(triehash)에 쓰는 keccak256 hasher
+pub struct KeccakHasher;
+
+impl hash_db::Hasher for KeccakHasher {
+    type Out = H256;
+    type StdHasher = PlainHasher;
+    const LENGTH: usize = 32;
+
+    fn hash(x: &[u8]) -> H256 {
+        keccak256(x)
+    }
+}
+
+/// 키가 이미 해시이므로 앞의 8바이트만 섞어 쓰는 `std::hash::Hasher`
+#[derive(Default)]
+pub struct PlainHasher(u64);
+
+impl Hasher for PlainHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for (index, byte) in bytes.iter().take(8).enumerate() {
+            self.0 ^= (*byte as u64) << (index * 8);
+        }
+    }
+}
+
+/// 순서가 있는 목록의 root (transactionsRoot, receiptsRoot). 키는 `rlp(index)`다.
+pub fn ordered_trie_root<I, A>(items: I) -> H256
+    where I: IntoIterator<Item = A>, A: AsRef<[u8]> {
+    triehash::ordered_trie_root::<KeccakHasher, I>(items)
+}
+
+/// 키를 keccak256으로 해시해서 넣는 trie의 root (stateRoot, storageRoot)
+pub fn sec_trie_root<I, A, B>(items: I) -> H256
+    where I: IntoIterator<Item = (A, B)>, A: AsRef<[u8]>, B: AsRef<[u8]> {
+    triehash::sec_trie_root::<KeccakHasher, I, A, B>(items)
+}
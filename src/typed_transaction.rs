@@ -0,0 +1,350 @@
+use std::convert::TryFrom;
+use crypto::hash::keccak256;
+use ethereum_types::{Address, BigEndianHash, H256, U256};
+use rlp::{DecoderError, Rlp, RlpStream};
+use serde::Serialize;
+use web3_signer::error::RecoveryError;
+use crate::access_list_transaction::{AccessListItem, AccessListTransaction};
+use crate::blob_transaction::BlobTransaction;
+use crate::dynamic_fee_transaction::DynamicFeeTransaction;
+use crate::miner::BLOCK_GAS_LIMIT;
+use crate::raw_transaction::{MetamaskRawTransaction, RawTransaction};
+use crate::set_code_transaction::SetCodeTransaction;
+
+pub const LEGACY_TX_TYPE: u8 = 0x00;
+pub const ACCESS_LIST_TX_TYPE: u8 = 0x01;
+pub const DYNAMIC_FEE_TX_TYPE: u8 = 0x02;
+pub const BLOB_TX_TYPE: u8 = 0x03;
+pub const SET_CODE_TX_TYPE: u8 = 0x04;
+
+/// intrinsic gas (EIP-2028, EIP-2930, EIP-3860, EIP-7702)
+pub const TX_GAS: u64 = 21000;
+pub const TX_CREATE_GAS: u64 = 32000;
+pub const TX_DATA_ZERO_GAS: u64 = 4;
+pub const TX_DATA_NON_ZERO_GAS: u64 = 16;
+pub const TX_INIT_CODE_WORD_GAS: u64 = 2;
+pub const TX_ACCESS_LIST_ADDRESS_GAS: u64 = 2400;
+pub const TX_ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1900;
+pub const TX_AUTHORIZATION_GAS: u64 = 25000;
+
+/// EIP-2718 트랜잭션 envelope
+/// 첫 바이트가 `0xc0` 이상이면 legacy RLP 리스트, 아니면 `type || payload` 형태다.
+/// JSON으로는 `type` 필드와 함께 직렬화한다.
+#[derive(Serialize, Clone)]
+#[serde(tag="type")]
+pub enum TypedTransaction {
+    #[serde(rename="0x0")]
+    Legacy(RawTransaction),
+    #[serde(rename="0x1")]
+    AccessList(AccessListTransaction),
+    #[serde(rename="0x2")]
+    DynamicFee(DynamicFeeTransaction),
+    #[serde(rename="0x3")]
+    Blob(BlobTransaction),
+    #[serde(rename="0x4")]
+    SetCode(SetCodeTransaction),
+}
+
+impl TypedTransaction {
+    /// `eth_sendRawTransaction`으로 받은 바이트를 해석한다.
+    pub fn decode(raw: &[u8]) -> Result<Self, DecoderError> {
+        match raw.first() {
+            None => Err(DecoderError::RlpIsTooShort),
+            Some(&first) if first >= 0xc0 => {
+                let mrtx = rlp::decode::<MetamaskRawTransaction>(raw)?;
+                Ok(TypedTransaction::Legacy(RawTransaction::try_from(mrtx)?))
+            }
+            Some(&ACCESS_LIST_TX_TYPE) => Ok(TypedTransaction::AccessList(rlp::decode(&raw[1..])?)),
+            Some(&DYNAMIC_FEE_TX_TYPE) => Ok(TypedTransaction::DynamicFee(rlp::decode(&raw[1..])?)),
+            Some(&BLOB_TX_TYPE) => Ok(TypedTransaction::Blob(BlobTransaction::decode_payload(&raw[1..])?)),
+            Some(&SET_CODE_TX_TYPE) => Ok(TypedTransaction::SetCode(rlp::decode(&raw[1..])?)),
+            Some(_) => Err(DecoderError::Custom("unsupported transaction type")),
+        }
+    }
+
+    pub fn tx_type(&self) -> u8 {
+        match self {
+            TypedTransaction::Legacy(_) => LEGACY_TX_TYPE,
+            TypedTransaction::AccessList(_) => ACCESS_LIST_TX_TYPE,
+            TypedTransaction::DynamicFee(_) => DYNAMIC_FEE_TX_TYPE,
+            TypedTransaction::Blob(_) => BLOB_TX_TYPE,
+            TypedTransaction::SetCode(_) => SET_CODE_TX_TYPE,
+        }
+    }
+
+    /// 블록에 들어가는 canonical form으로 인코딩한다. blob 트랜잭션은 sidecar를 뺀다.
+    pub fn encode(&self) -> Vec<u8> {
+        let payload = match self {
+            TypedTransaction::Legacy(tx) => return rlp::encode(tx).to_vec(),
+            TypedTransaction::AccessList(tx) => rlp::encode(tx),
+            TypedTransaction::DynamicFee(tx) => rlp::encode(tx),
+            TypedTransaction::Blob(tx) => rlp::encode(tx),
+            TypedTransaction::SetCode(tx) => rlp::encode(tx),
+        };
+        with_tx_type(self.tx_type(), payload.as_ref())
+    }
+
+    /// 트랜잭션 해시. `raw`는 받은 바이트 그대로다.
+    /// network form의 blob 트랜잭션만 sidecar를 뺀 canonical form으로 다시 인코딩해서 해시한다.
+    pub fn hash(&self, raw: &[u8]) -> H256 {
+        match self {
+            TypedTransaction::Blob(tx) if tx.sidecar.is_some() => tx.hash(),
+            _ => keccak256(raw),
+        }
+    }
+
+    /// 서명과 상관없는 type별 검사. 문제가 있으면 이유를 돌려준다.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            TypedTransaction::Blob(tx) => tx.validate()?,
+            TypedTransaction::SetCode(tx) if tx.authorization_list.is_empty() => return Err("set code transaction with empty auth list".to_string()),
+            _ => {}
+        }
+        self.check_fee_cap(None)?;
+        if self.gas() > U256::from(BLOCK_GAS_LIMIT) {
+            return Err(format!("exceeds block gas limit: have {}, limit {}", self.gas(), BLOCK_GAS_LIMIT));
+        }
+        let intrinsic_gas = self.intrinsic_gas();
+        if self.gas() < U256::from(intrinsic_gas) {
+            return Err(format!("intrinsic gas too low: have {}, want {}", self.gas(), intrinsic_gas));
+        }
+        Ok(())
+    }
+
+    /// 서명에 들어 있는 chain id
+    /// EIP-155 이전 legacy 트랜잭션은 chain id가 없다. (`None`)
+    pub fn chain_id(&self) -> Option<u64> {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.chain_id(),
+            TypedTransaction::AccessList(tx) => Some(tx.chain_id.as_u64()),
+            TypedTransaction::DynamicFee(tx) => Some(tx.chain_id.as_u64()),
+            TypedTransaction::Blob(tx) => Some(tx.chain_id.as_u64()),
+            TypedTransaction::SetCode(tx) => Some(tx.chain_id.as_u64()),
+        }
+    }
+
+    /// 받는 주소. 컨트랙트를 만드는 트랜잭션이면 `None`
+    pub fn to(&self) -> Option<Address> {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.to,
+            TypedTransaction::AccessList(tx) => tx.to,
+            TypedTransaction::DynamicFee(tx) => tx.to,
+            TypedTransaction::Blob(tx) => Some(tx.to),
+            TypedTransaction::SetCode(tx) => Some(tx.to),
+        }
+    }
+
+    pub fn nonce(&self) -> u64 {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.nonce,
+            TypedTransaction::AccessList(tx) => tx.nonce.as_u64(),
+            TypedTransaction::DynamicFee(tx) => tx.nonce.as_u64(),
+            TypedTransaction::Blob(tx) => tx.nonce.as_u64(),
+            TypedTransaction::SetCode(tx) => tx.nonce.as_u64(),
+        }
+    }
+
+    /// gas limit
+    pub fn gas(&self) -> U256 {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.gas,
+            TypedTransaction::AccessList(tx) => tx.gas.as_u64().into(),
+            TypedTransaction::DynamicFee(tx) => tx.gas.as_u64().into(),
+            TypedTransaction::Blob(tx) => tx.gas.as_u64().into(),
+            TypedTransaction::SetCode(tx) => tx.gas.as_u64().into(),
+        }
+    }
+
+    pub fn value(&self) -> U256 {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.value,
+            TypedTransaction::AccessList(tx) => tx.value,
+            TypedTransaction::DynamicFee(tx) => tx.value,
+            TypedTransaction::Blob(tx) => tx.value,
+            TypedTransaction::SetCode(tx) => tx.value,
+        }
+    }
+
+    pub fn input(&self) -> &[u8] {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.data.as_slice(),
+            TypedTransaction::AccessList(tx) => tx.input.as_slice(),
+            TypedTransaction::DynamicFee(tx) => tx.input.as_slice(),
+            TypedTransaction::Blob(tx) => tx.input.as_slice(),
+            TypedTransaction::SetCode(tx) => tx.input.as_slice(),
+        }
+    }
+
+    /// gas 하나에 낼 수 있는 최대 가격. legacy, EIP-2930은 `gasPrice`, 나머지는 `maxFeePerGas`
+    pub fn max_fee_per_gas(&self) -> U256 {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.gas_price,
+            TypedTransaction::AccessList(tx) => tx.gas_price,
+            TypedTransaction::DynamicFee(tx) => tx.max_fee_per_gas,
+            TypedTransaction::Blob(tx) => tx.max_fee_per_gas,
+            TypedTransaction::SetCode(tx) => tx.max_fee_per_gas,
+        }
+    }
+
+    /// 블록 생성자에게 주는 gas 하나당 최대 팁. EIP-1559 이전 형식(legacy, EIP-2930)은 `None`
+    pub fn max_priority_fee_per_gas(&self) -> Option<U256> {
+        match self {
+            TypedTransaction::DynamicFee(tx) => Some(tx.max_priority_fee_per_gas),
+            TypedTransaction::Blob(tx) => Some(tx.max_priority_fee_per_gas),
+            TypedTransaction::SetCode(tx) => Some(tx.max_priority_fee_per_gas),
+            _ => None,
+        }
+    }
+
+    /// EIP-1559 수수료 상한을 확인한다. (legacy, EIP-2930은 확인하지 않는다)
+    /// - `maxPriorityFeePerGas`가 `maxFeePerGas`보다 크면 안 된다.
+    /// - `base_fee`가 주어지면 `maxFeePerGas`가 그보다 작으면 안 된다.
+    pub fn check_fee_cap(&self, base_fee: Option<U256>) -> Result<(), String> {
+        let max_priority_fee_per_gas = match self.max_priority_fee_per_gas() {
+            Some(max_priority_fee_per_gas) => max_priority_fee_per_gas,
+            None => return Ok(()),
+        };
+        let max_fee_per_gas = self.max_fee_per_gas();
+        if max_priority_fee_per_gas > max_fee_per_gas {
+            return Err(format!("max priority fee per gas higher than max fee per gas: maxPriorityFeePerGas: {}, maxFeePerGas: {}",
+                max_priority_fee_per_gas, max_fee_per_gas));
+        }
+        match base_fee {
+            Some(base_fee) if max_fee_per_gas < base_fee =>
+                Err(format!("max fee per gas less than block base fee: maxFeePerGas: {}, baseFee: {}", max_fee_per_gas, base_fee)),
+            _ => Ok(()),
+        }
+    }
+
+    /// 실제로 낸 gas 가격. base fee가 없으면 `max_fee_per_gas`와 같다.
+    pub fn effective_gas_price(&self, base_fee: Option<U256>) -> U256 {
+        let max_priority_fee_per_gas = match self.max_priority_fee_per_gas() {
+            Some(max_priority_fee_per_gas) => max_priority_fee_per_gas,
+            None => return self.max_fee_per_gas(),
+        };
+        match base_fee {
+            Some(base_fee) => self.max_fee_per_gas().min(base_fee.saturating_add(max_priority_fee_per_gas)),
+            None => self.max_fee_per_gas(),
+        }
+    }
+
+    pub fn access_list(&self) -> &[AccessListItem] {
+        match self {
+            TypedTransaction::Legacy(_) => &[],
+            TypedTransaction::AccessList(tx) => tx.access_list.as_slice(),
+            TypedTransaction::DynamicFee(tx) => tx.access_list.as_slice(),
+            TypedTransaction::Blob(tx) => tx.access_list.as_slice(),
+            TypedTransaction::SetCode(tx) => tx.access_list.as_slice(),
+        }
+    }
+
+    /// 실행 전에 드는 gas. EVM을 실행하지 않으므로 이 값을 사용한 gas로 본다.
+    pub fn intrinsic_gas(&self) -> u64 {
+        let input = self.input();
+        let zero_bytes = input.iter().filter(|byte| **byte == 0).count() as u64;
+        let non_zero_bytes = input.len() as u64 - zero_bytes;
+        let mut gas = TX_GAS + zero_bytes * TX_DATA_ZERO_GAS + non_zero_bytes * TX_DATA_NON_ZERO_GAS;
+        if self.to().is_none() {
+            gas += TX_CREATE_GAS + (input.len() as u64 + 31) / 32 * TX_INIT_CODE_WORD_GAS;
+        }
+        for item in self.access_list() {
+            gas += TX_ACCESS_LIST_ADDRESS_GAS + item.storage_keys.len() as u64 * TX_ACCESS_LIST_STORAGE_KEY_GAS;
+        }
+        if let TypedTransaction::SetCode(tx) = self {
+            gas += tx.authorization_list.len() as u64 * TX_AUTHORIZATION_GAS;
+        }
+        gas
+    }
+
+    /// blob 트랜잭션이 아니면 0
+    pub fn blob_gas_used(&self) -> u64 {
+        match self {
+            TypedTransaction::Blob(tx) => tx.blob_gas_used(),
+            _ => 0,
+        }
+    }
+
+    /// 보내는 계정에 있어야 하는 최소 잔액. `gas * max_fee_per_gas + value (+ blob gas * max_fee_per_blob_gas)`
+    pub fn cost(&self) -> U256 {
+        let mut cost = self.gas().saturating_mul(self.max_fee_per_gas()).saturating_add(self.value());
+        if let TypedTransaction::Blob(tx) = self {
+            cost = cost.saturating_add(U256::from(tx.blob_gas_used()).saturating_mul(tx.max_fee_per_blob_gas));
+        }
+        cost
+    }
+
+    /// 컨트랙트를 만드는 트랜잭션이면 만들어질 컨트랙트 주소
+    pub fn contract_address(&self, sender: &Address) -> Option<Address> {
+        match self.to() {
+            Some(_) => None,
+            None => Some(create_address(sender, self.nonce())),
+        }
+    }
+
+    /// 서명할 때 쓰는 해시
+    pub fn signing_hash(&self) -> H256 {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.signing_hash(),
+            TypedTransaction::AccessList(tx) => tx.signing_hash(),
+            TypedTransaction::DynamicFee(tx) => tx.signing_hash(),
+            TypedTransaction::Blob(tx) => tx.signing_hash(),
+            TypedTransaction::SetCode(tx) => tx.signing_hash(),
+        }
+    }
+
+    /// 서명의 `s`
+    pub fn signature_s(&self) -> U256 {
+        match self {
+            TypedTransaction::Legacy(tx) => U256::from_big_endian(tx.s.as_slice()),
+            TypedTransaction::AccessList(tx) => tx.s,
+            TypedTransaction::DynamicFee(tx) => tx.s,
+            TypedTransaction::Blob(tx) => tx.s,
+            TypedTransaction::SetCode(tx) => tx.s,
+        }
+    }
+
+    pub fn sender(&self) -> Result<Address, RecoveryError> {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.sender(),
+            TypedTransaction::AccessList(tx) => tx.sender(),
+            TypedTransaction::DynamicFee(tx) => tx.sender(),
+            TypedTransaction::Blob(tx) => tx.sender(),
+            TypedTransaction::SetCode(tx) => tx.sender(),
+        }
+    }
+}
+
+/// CREATE로 만들어지는 컨트랙트 주소. `keccak256(rlp([sender, nonce]))[12..]`
+pub fn create_address(sender: &Address, nonce: u64) -> Address {
+    let mut stream = RlpStream::new_list(2);
+    stream.append(sender);
+    stream.append(&nonce);
+    Address::from_slice(&keccak256(stream.as_raw()).as_bytes()[12..])
+}
+
+/// `to`가 빈 문자열이면 컨트랙트 생성이다.
+pub(crate) fn decode_to(rlp: &Rlp) -> Result<Option<Address>, DecoderError> {
+    match rlp.is_empty() {
+        true => Ok(None),
+        false => rlp.as_val().map(Some),
+    }
+}
+
+pub(crate) fn append_to(s: &mut RlpStream, to: &Option<Address>) {
+    match to {
+        Some(to) => s.append(to),
+        None => s.append_empty_data(),
+    };
+}
+
+/// typed 트랜잭션의 바이트 형식. `tx_type || payload`
+pub(crate) fn with_tx_type(tx_type: u8, payload: &[u8]) -> Vec<u8> {
+    let mut data = vec![tx_type];
+    data.extend_from_slice(payload);
+    data
+}
+
+/// RLP 정수로 받은 r, s를 32바이트로 맞춘다.
+pub(crate) fn h256_from_u256(value: &U256) -> H256 {
+    H256::from_uint(value)
+}
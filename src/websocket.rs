@@ -0,0 +1,71 @@
+use std::net::TcpStream;
+use std::time::Duration;
+use log::{info, warn};
+use tungstenite::{Message, WebSocket};
+use crate::handler::handle_rpc_payload;
+use crate::methods::RpcContext;
+use crate::node::Node;
+use crate::request::RpcId;
+use crate::response::{new_json_rpc_error_response, RpcError};
+
+/// 요청을 기다리는 동안 구독 알림을 확인하는 주기
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// TCP 연결에서 WebSocket handshake를 하고 연결이 끊길 때까지 처리한다.
+pub fn accept(stream: TcpStream, peer_addr: &str, node: &Node) {
+    match tungstenite::accept(stream) {
+        Ok(socket) => serve(socket, peer_addr, node),
+        Err(e) => warn!("[WEBSOCKET] ip={} handshake failed: {}", peer_addr, e),
+    }
+}
+
+/// handshake를 마친 연결에서 JSON-RPC 요청에 응답하고, eth_subscribe 알림을 보낸다.
+/// 연결이 끊기거나 노드가 멈추면 돌아오며, 그 연결의 구독은 모두 지운다.
+pub fn serve(mut socket: WebSocket<TcpStream>, peer_addr: &str, node: &Node) {
+    if let Err(e) = socket.get_ref().set_read_timeout(Some(POLL_INTERVAL)) {
+        warn!("[WEBSOCKET] ip={} could not set the read timeout: {}", peer_addr, e);
+        return;
+    }
+    let (subscriber, notifications) = node.subscriptions.connect();
+    info!("[WEBSOCKET] ip={} connected", peer_addr);
+    loop {
+        if node.lifecycle.is_shutting_down() {
+            let _ = socket.close(None);
+            let _ = socket.flush();
+            break;
+        }
+        if notifications.try_iter().any(|notification| socket.send(Message::Text(notification)).is_err()) {
+            break;
+        }
+        let data = match socket.read() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Binary(data)) => match String::from_utf8(data) {
+                Ok(text) => text,
+                Err(_) => {
+                    warn!("[WEBSOCKET] ip={} binary message is not utf-8", peer_addr);
+                    match socket.send(Message::Text(new_json_rpc_error_response(&RpcId::Null, &RpcError::parse_error()))) {
+                        Ok(_) => continue,
+                        Err(_) => break,
+                    }
+                }
+            },
+            Ok(Message::Close(_)) => break,
+            Ok(_) => continue,
+            Err(tungstenite::Error::Io(ref e))
+                if e.kind() == std::io::ErrorKind::WouldBlock || e.kind() == std::io::ErrorKind::TimedOut => continue,
+            Err(_) => break,
+        };
+        let response = match node.lifecycle.enter() {
+            Some(_in_flight) => {
+                let ctx = RpcContext { node, channel: None, subscriber: Some(&subscriber) };
+                handle_rpc_payload(data.as_str(), peer_addr, &ctx)
+            }
+            None => new_json_rpc_error_response(&RpcId::Null, &RpcError::resource_unavailable("node is shutting down")),
+        };
+        if !response.is_empty() && socket.send(Message::Text(response)).is_err() {
+            break;
+        }
+    }
+    let removed = node.subscriptions.disconnect(subscriber.connection_id);
+    info!("[WEBSOCKET] ip={} disconnected, {} subscription(s) removed", peer_addr, removed);
+}
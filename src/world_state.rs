@@ -0,0 +1,228 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+use crypto::hash::keccak256;
+use ethereum_types::{Address, BigEndianHash, H256, U256, U64};
+use rlp::{Encodable, RlpStream};
+use serde::Serialize;
+use crate::block_tag::BlockTag;
+use crate::bytes::Bytes;
+use crate::trie::sec_trie_root;
+use crate::typed_transaction::TypedTransaction;
+
+/// 계정 하나의 상태
+/// # [Fields]
+/// - `balance: U256`                   - 잔액 (wei)
+/// - `nonce: U64`                      - 이 계정이 보낸 트랜잭션 수
+/// - `code: Bytes`                     - 코드 (EIP-7702 위임 코드 포함)
+/// - `storage: BTreeMap<H256, H256>`   - 스토리지. 없는 슬롯은 0이다.
+#[derive(Serialize, Clone, Default, Debug)]
+pub struct AccountState {
+    pub balance: U256,
+    pub nonce: U64,
+    pub code: Bytes,
+    pub storage: BTreeMap<H256, H256>,
+}
+
+impl AccountState {
+    pub fn storage_root(&self) -> H256 {
+        sec_trie_root(self.storage.iter().map(|(key, value)| (*key, rlp::encode(&value.into_uint()))))
+    }
+}
+
+/// state trie에 들어가는 계정. `[nonce, balance, storageRoot, codeHash]`
+impl Encodable for AccountState {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(4);
+        s.append(&self.nonce);
+        s.append(&self.balance);
+        s.append(&self.storage_root());
+        s.append(&keccak256(self.code.as_slice()));
+    }
+}
+
+type Accounts = HashMap<Address, AccountState>;
+
+fn state_root(accounts: &Accounts) -> H256 {
+    sec_trie_root(accounts.iter().map(|(address, account)| (*address, rlp::encode(account))))
+}
+
+struct StateInner {
+    /// 최신 블록 번호
+    head: u64,
+    /// 받은 트랜잭션까지 모두 반영한 상태 (pending)
+    accounts: Accounts,
+    /// 블록 번호별로 확정된 상태. 0번(genesis)은 항상 있다.
+    snapshots: BTreeMap<u64, Accounts>,
+}
+
+/// 계정별 잔액, nonce, 코드, 스토리지
+/// EVM을 실행하지 않으므로 트랜잭션은 nonce와 잔액만 바꾸고, 코드는 EIP-7702 위임이나 직접 넣은 값만 있다.
+/// 한 번도 건드리지 않은 계정은 `default_balance`를 가진 것으로 본다.
+/// 트랜잭션은 pending 상태에 먼저 반영되고, 블록을 만들 때(`commit`) 그 블록의 상태로 확정된다.
+pub struct WorldState {
+    default_balance: U256,
+    inner: Mutex<StateInner>,
+}
+
+impl WorldState {
+    /// `alloc`에 있는 계정은 주어진 잔액으로 시작한다.
+    pub fn new(default_balance: U256, alloc: &BTreeMap<Address, U256>) -> Self {
+        let accounts: Accounts = alloc.iter()
+            .map(|(address, balance)| (*address, AccountState { balance: *balance, ..AccountState::default() }))
+            .collect();
+        let mut snapshots = BTreeMap::new();
+        snapshots.insert(0, accounts.clone());
+        Self {
+            default_balance,
+            inner: Mutex::new(StateInner { head: 0, accounts, snapshots }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, StateInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn new_account(&self) -> AccountState {
+        AccountState { balance: self.default_balance, ..AccountState::default() }
+    }
+
+    /// 최신 블록 번호
+    pub fn head(&self) -> u64 {
+        self.lock().head
+    }
+
+    /// `block`의 계정 상태. 아직 없는 블록이면 `None`
+    /// `pending`은 블록에 아직 들어가지 않은 트랜잭션까지 반영한 상태다.
+    pub fn account(&self, address: &Address, block: BlockTag) -> Option<AccountState> {
+        let inner = self.lock();
+        let number = block.resolve(inner.head);
+        let accounts = if block == BlockTag::Pending {
+            &inner.accounts
+        } else if number > inner.head {
+            return None;
+        } else {
+            inner.snapshots.range(..=number).next_back().map(|(_, accounts)| accounts)?
+        };
+        Some(accounts.get(address).cloned().unwrap_or_else(|| self.new_account()))
+    }
+
+    pub fn balance(&self, address: &Address, block: BlockTag) -> Option<U256> {
+        self.account(address, block).map(|account| account.balance)
+    }
+
+    pub fn nonce(&self, address: &Address, block: BlockTag) -> Option<U64> {
+        self.account(address, block).map(|account| account.nonce)
+    }
+
+    pub fn code(&self, address: &Address, block: BlockTag) -> Option<Bytes> {
+        self.account(address, block).map(|account| account.code)
+    }
+
+    pub fn storage(&self, address: &Address, key: &H256, block: BlockTag) -> Option<H256> {
+        self.account(address, block).map(|account| account.storage.get(key).cloned().unwrap_or_default())
+    }
+
+    /// pending 상태의 계정을 바꾼다. 다음 블록을 만들 때 확정된다.
+    pub fn update<F: FnOnce(&mut AccountState)>(&self, address: Address, f: F) {
+        let new_account = self.new_account();
+        let mut inner = self.lock();
+        f(inner.accounts.entry(address).or_insert(new_account));
+    }
+
+    /// 블록을 만들지 않고 pending 상태와 최신 블록의 상태를 함께 바꾼다.
+    /// MockServer를 쓰는 쪽에서 `set_balance` 등으로 계정을 미리 준비할 때 쓴다.
+    fn set<F: Fn(&mut AccountState)>(&self, address: Address, f: F) {
+        let new_account = self.new_account();
+        let mut inner = self.lock();
+        f(inner.accounts.entry(address).or_insert_with(|| new_account.clone()));
+        let head = inner.head;
+        if let Some(accounts) = inner.snapshots.get_mut(&head) {
+            f(accounts.entry(address).or_insert(new_account));
+        }
+    }
+
+    pub fn set_balance(&self, address: Address, balance: U256) {
+        self.set(address, |account| account.balance = balance);
+    }
+
+    pub fn set_nonce(&self, address: Address, nonce: u64) {
+        self.set(address, |account| account.nonce = nonce.into());
+    }
+
+    pub fn set_code(&self, address: Address, code: Bytes) {
+        self.set(address, |account| account.code = code.clone());
+    }
+
+    /// 0을 넣으면 슬롯을 지운다.
+    pub fn set_storage(&self, address: Address, key: H256, value: H256) {
+        self.set(address, |account| match value.is_zero() {
+            true => { account.storage.remove(&key); }
+            false => { account.storage.insert(key, value); }
+        });
+    }
+
+    /// 트랜잭션을 현재 상태에 반영한다. 받을 수 없는 트랜잭션이면 이유를 돌려준다.
+    /// - nonce가 계정의 nonce와 다르면 거절한다. 큐가 없으므로 나중 nonce를 미리 받지 않는다.
+    /// - `gas * max_fee_per_gas + value`보다 잔액이 적으면 거절한다.
+    /// - `max_priority_fee_per_gas`가 `max_fee_per_gas`보다 크거나 `max_fee_per_gas`가 `base_fee`보다 작으면 거절한다.
+    /// - blob 트랜잭션의 `max_fee_per_blob_gas`가 `blob_base_fee`보다 작으면 거절한다.
+    /// - 수수료는 intrinsic gas만큼만 낸다. blob gas는 `blob_base_fee`로 낸다.
+    pub fn apply_transaction(&self, sender: &Address, tx: &TypedTransaction, base_fee: Option<U256>, blob_base_fee: U256) -> Result<(), String> {
+        let new_account = self.new_account();
+        let mut inner = self.lock();
+        let account = inner.accounts.entry(*sender).or_insert_with(|| new_account.clone());
+        if tx.nonce() < account.nonce.as_u64() {
+            return Err(format!("nonce too low: address {:?}, tx: {} state: {}", sender, tx.nonce(), account.nonce));
+        }
+        if tx.nonce() > account.nonce.as_u64() {
+            return Err(format!("nonce too high: address {:?}, tx: {} state: {}", sender, tx.nonce(), account.nonce));
+        }
+        let next_nonce = tx.nonce().checked_add(1)
+            .ok_or_else(|| format!("nonce has max value: address {:?}, nonce: {}", sender, tx.nonce()))?;
+        tx.check_fee_cap(base_fee).map_err(|reason| format!("{}: address {:?}", reason, sender))?;
+        if let TypedTransaction::Blob(btx) = tx {
+            if btx.max_fee_per_blob_gas < blob_base_fee {
+                return Err(format!("max fee per blob gas less than block blob gas fee: address {:?} blobGasFeeCap: {}, blobBaseFee: {}",
+                    sender, btx.max_fee_per_blob_gas, blob_base_fee));
+            }
+        }
+        let cost = tx.cost();
+        if account.balance < cost {
+            return Err(format!("insufficient funds for gas * price + value: address {:?} have {} want {}", sender, account.balance, cost));
+        }
+
+        let mut fee = U256::from(tx.intrinsic_gas()).saturating_mul(tx.effective_gas_price(base_fee));
+        if let TypedTransaction::Blob(btx) = tx {
+            fee = fee.saturating_add(U256::from(btx.blob_gas_used()).saturating_mul(blob_base_fee));
+        }
+        account.nonce = next_nonce.into();
+        account.balance = account.balance.saturating_sub(fee).saturating_sub(tx.value());
+
+        let (recipient, created) = match tx.to() {
+            Some(to) => (to, false),
+            None => (tx.contract_address(sender).unwrap_or_default(), true),
+        };
+        let recipient = inner.accounts.entry(recipient).or_insert(new_account);
+        recipient.balance = recipient.balance.saturating_add(tx.value());
+        if created {
+            // EIP-161: 새로 만든 컨트랙트의 nonce는 1부터 시작한다.
+            recipient.nonce = U64::one();
+        }
+        Ok(())
+    }
+
+    /// pending 상태의 state root
+    pub fn state_root(&self) -> H256 {
+        state_root(&self.lock().accounts)
+    }
+
+    /// pending 상태를 `number`번 블록의 상태로 확정하고 state root를 돌려준다.
+    pub fn commit(&self, number: u64) -> H256 {
+        let mut inner = self.lock();
+        let accounts = inner.accounts.clone();
+        let root = state_root(&accounts);
+        inner.snapshots.insert(number, accounts);
+        inner.head = number;
+        root
+    }
+}